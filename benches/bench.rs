@@ -4,8 +4,10 @@ use self::setup::{
     generate_test_strings,
     BackendBenchmark,
     BenchBucket,
+    BenchBucketCachedHash,
     BenchSimple,
     BenchString,
+    BenchStringCachedHash,
     BENCH_LEN_STRINGS,
     BENCH_STRING_LEN,
 };
@@ -130,6 +132,8 @@ fn bench_get_or_intern_fill(c: &mut Criterion) {
     bench_for_backend::<BenchSimple>(&mut g);
     bench_for_backend::<BenchBucket>(&mut g);
     bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchBucketCachedHash>(&mut g);
+    bench_for_backend::<BenchStringCachedHash>(&mut g);
 }
 
 fn bench_get_or_intern_already_filled(c: &mut Criterion) {
@@ -178,6 +182,21 @@ fn bench_resolve_already_filled(c: &mut Criterion) {
                 )
             },
         );
+        // Resolves from a single interner that stays warm in the cache so that
+        // only the cost of resolving itself is measured.
+        g.bench_with_input(
+            format!("{}/reused", BB::NAME),
+            &(BENCH_LEN_STRINGS, BENCH_STRING_LEN),
+            |bencher, &(len_words, word_len)| {
+                let words = generate_test_strings(len_words, word_len);
+                let (interner, word_ids) = BB::setup_filled_with_ids(&words);
+                bencher.iter(|| {
+                    for &word_id in &word_ids {
+                        black_box(interner.resolve(black_box(word_id)));
+                    }
+                })
+            },
+        );
     }
     bench_for_backend::<BenchSimple>(&mut g);
     bench_for_backend::<BenchBucket>(&mut g);
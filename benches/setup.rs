@@ -5,6 +5,10 @@ use string_interner::{
         SimpleBackend,
         StringBackend,
     },
+    dedup::{
+        CachedHash,
+        DedupKey,
+    },
     DefaultSymbol,
     StringInterner,
 };
@@ -85,28 +89,34 @@ pub const BENCH_LEN_STRINGS: usize = 100_000;
 pub const BENCH_STRING_LEN: usize = 5;
 
 type FxBuildHasher = fxhash::FxBuildHasher;
-type StringInternerWith<B> = StringInterner<DefaultSymbol, B, FxBuildHasher>;
+type StringInternerWith<B, K> = StringInterner<DefaultSymbol, B, FxBuildHasher, K>;
 
 pub trait BackendBenchmark {
     const NAME: &'static str;
     type Backend: Backend<DefaultSymbol>;
+    type Key: DedupKey<DefaultSymbol>;
 
-    fn setup() -> StringInternerWith<Self::Backend> {
-        <StringInternerWith<Self::Backend>>::new()
+    fn setup() -> StringInternerWith<Self::Backend, Self::Key> {
+        <StringInternerWith<Self::Backend, Self::Key>>::new()
     }
 
-    fn setup_with_capacity(cap: usize) -> StringInternerWith<Self::Backend> {
-        <StringInternerWith<Self::Backend>>::with_capacity(cap)
+    fn setup_with_capacity(cap: usize) -> StringInternerWith<Self::Backend, Self::Key> {
+        <StringInternerWith<Self::Backend, Self::Key>>::with_capacity(cap)
     }
 
-    fn setup_filled(words: &[String]) -> StringInternerWith<Self::Backend> {
-        words.iter().collect::<StringInternerWith<Self::Backend>>()
+    fn setup_filled(words: &[String]) -> StringInternerWith<Self::Backend, Self::Key> {
+        words
+            .iter()
+            .collect::<StringInternerWith<Self::Backend, Self::Key>>()
     }
 
     fn setup_filled_with_ids(
         words: &[String],
-    ) -> (StringInternerWith<Self::Backend>, Vec<DefaultSymbol>) {
-        let mut interner = <StringInternerWith<Self::Backend>>::new();
+    ) -> (
+        StringInternerWith<Self::Backend, Self::Key>,
+        Vec<DefaultSymbol>,
+    ) {
+        let mut interner = <StringInternerWith<Self::Backend, Self::Key>>::new();
         let mut word_ids = Vec::new();
         for word in words {
             let word_id = interner.get_or_intern(word);
@@ -120,16 +130,33 @@ pub struct BenchBucket;
 impl BackendBenchmark for BenchBucket {
     const NAME: &'static str = "BucketBackend";
     type Backend = BucketBackend<DefaultSymbol>;
+    type Key = DefaultSymbol;
 }
 
 pub struct BenchSimple;
 impl BackendBenchmark for BenchSimple {
     const NAME: &'static str = "SimpleBackend";
     type Backend = SimpleBackend<DefaultSymbol>;
+    type Key = DefaultSymbol;
 }
 
 pub struct BenchString;
 impl BackendBenchmark for BenchString {
     const NAME: &'static str = "StringBackend";
     type Backend = StringBackend<DefaultSymbol>;
+    type Key = DefaultSymbol;
+}
+
+pub struct BenchBucketCachedHash;
+impl BackendBenchmark for BenchBucketCachedHash {
+    const NAME: &'static str = "BucketBackend/CachedHash";
+    type Backend = BucketBackend<DefaultSymbol>;
+    type Key = CachedHash<DefaultSymbol>;
+}
+
+pub struct BenchStringCachedHash;
+impl BackendBenchmark for BenchStringCachedHash {
+    const NAME: &'static str = "StringBackend/CachedHash";
+    type Backend = StringBackend<DefaultSymbol>;
+    type Key = CachedHash<DefaultSymbol>;
 }
@@ -0,0 +1,140 @@
+//! Derive macros for the [`string-interner`](https://docs.rs/string-interner) crate.
+//!
+//! Enable the `derive` feature of `string-interner` to use them through
+//! `string_interner::Symbol` instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input,
+    spanned::Spanned,
+    Data,
+    DeriveInput,
+    Error,
+    Fields,
+};
+
+/// Derives `string_interner::Symbol` for a newtype around another symbol.
+///
+/// The deriving type must be a tuple struct with a single field whose type
+/// implements `Symbol`. It must also implement `Copy` and `Eq` as required
+/// by `Symbol`. Conversions, including inline strings, are forwarded to the
+/// wrapped symbol so that the newtype has the same size and niche, e.g.
+/// `Option<VarName>` is as large as `VarName` if it wraps a `SymbolU32`.
+///
+/// # Attributes
+///
+/// - `#[symbol(debug)]`: Also derives `Debug` printing the symbol index,
+///   e.g. `VarName(3)`.
+///
+/// # Example
+///
+/// ```
+/// use string_interner::{symbol::SymbolU32, StringInterner, Symbol};
+///
+/// #[derive(Symbol, Copy, Clone, PartialEq, Eq, Hash)]
+/// #[symbol(debug)]
+/// struct VarName(SymbolU32);
+///
+/// let mut interner = StringInterner::<VarName>::new();
+/// let x = interner.get_or_intern("x");
+/// assert_eq!(interner.resolve(x), Some("x"));
+/// assert_eq!(format!("{:?}", x), "VarName(0)");
+/// assert_eq!(core::mem::size_of::<Option<VarName>>(), 4);
+/// ```
+#[proc_macro_derive(Symbol, attributes(symbol))]
+pub fn derive_symbol(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_symbol(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Expands `#[derive(Symbol)]` for the given input.
+fn expand_symbol(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let debug = parse_symbol_attrs(&input)?;
+    let inner =
+        match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    &fields.unnamed[0].ty
+                }
+                fields => return Err(Error::new(
+                    fields.span(),
+                    "#[derive(Symbol)] requires a tuple struct with exactly one field",
+                )),
+            },
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "#[derive(Symbol)] can only be used on tuple structs",
+                ))
+            }
+        };
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let symbol_impl = quote! {
+        impl #impl_generics ::string_interner::Symbol for #ident #ty_generics #where_clause {
+            #[inline]
+            fn try_from_usize(index: ::core::primitive::usize) -> ::core::option::Option<Self> {
+                <#inner as ::string_interner::Symbol>::try_from_usize(index).map(Self)
+            }
+
+            #[inline]
+            fn to_usize(self) -> ::core::primitive::usize {
+                <#inner as ::string_interner::Symbol>::to_usize(self.0)
+            }
+
+            const INLINE_CAPACITY: ::core::primitive::usize =
+                <#inner as ::string_interner::Symbol>::INLINE_CAPACITY;
+
+            #[inline]
+            fn try_inline(string: &::core::primitive::str) -> ::core::option::Option<Self> {
+                <#inner as ::string_interner::Symbol>::try_inline(string).map(Self)
+            }
+
+            #[inline]
+            fn inline_str(&self) -> ::core::option::Option<&::core::primitive::str> {
+                <#inner as ::string_interner::Symbol>::inline_str(&self.0)
+            }
+        }
+    };
+    let debug_impl = debug.then(|| {
+        let name = ident.to_string();
+        quote! {
+            impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_tuple(#name)
+                        .field(&<#inner as ::string_interner::Symbol>::to_usize(self.0))
+                        .finish()
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        #symbol_impl
+        #debug_impl
+    })
+}
+
+/// Parses the `#[symbol(..)]` attributes of the input.
+///
+/// Returns `true` if `Debug` shall be derived.
+fn parse_symbol_attrs(input: &DeriveInput) -> Result<bool, Error> {
+    let mut debug = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("symbol"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("debug") {
+                debug = true;
+                return Ok(())
+            }
+            Err(meta.error("unsupported symbol attribute, expected `debug`"))
+        })?;
+    }
+    Ok(debug)
+}
@@ -0,0 +1,73 @@
+use core::mem::size_of;
+use string_interner::{
+    backend::StringBackend,
+    symbol::{
+        InlineSymbolU64,
+        SymbolU16,
+        SymbolU32,
+    },
+    DefaultHashBuilder,
+    StringInterner,
+    Symbol,
+};
+
+#[derive(Symbol, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct VarName(SymbolU32);
+
+#[derive(Symbol, Copy, Clone, PartialEq, Eq, Hash)]
+#[symbol(debug)]
+struct TypeName(SymbolU16);
+
+#[derive(Symbol, Copy, Clone, PartialEq, Eq, Hash)]
+#[symbol(debug)]
+struct FieldName(VarName);
+
+#[derive(Symbol, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Ident(InlineSymbolU64);
+
+#[test]
+fn forwards_conversions() {
+    assert_eq!(VarName::try_from_usize(42).map(VarName::to_usize), Some(42));
+    assert_eq!(TypeName::try_from_usize(u16::MAX as usize), None);
+    assert_eq!(FieldName::try_from_usize(7).map(Symbol::to_usize), Some(7));
+}
+
+#[test]
+fn preserves_niche() {
+    assert_eq!(size_of::<VarName>(), size_of::<SymbolU32>());
+    assert_eq!(size_of::<Option<VarName>>(), size_of::<SymbolU32>());
+    assert_eq!(size_of::<Option<TypeName>>(), size_of::<SymbolU16>());
+    assert_eq!(size_of::<Option<FieldName>>(), size_of::<SymbolU32>());
+}
+
+#[test]
+fn debug_works() {
+    assert_eq!(
+        format!("{:?}", TypeName::try_from_usize(3).unwrap()),
+        "TypeName(3)"
+    );
+    assert_eq!(
+        format!("{:?}", FieldName::try_from_usize(5).unwrap()),
+        "FieldName(5)"
+    );
+}
+
+#[test]
+fn interning_works() {
+    let mut interner =
+        StringInterner::<TypeName, StringBackend<TypeName>, DefaultHashBuilder>::new();
+    let foo = interner.get_or_intern("Foo");
+    let bar = interner.get_or_intern("Bar");
+    assert_ne!(foo, bar);
+    assert_eq!(interner.get_or_intern("Foo"), foo);
+    assert_eq!(interner.resolve(bar), Some("Bar"));
+}
+
+#[test]
+fn forwards_inline_strings() {
+    let mut interner = StringInterner::<Ident>::new();
+    let x = interner.get_or_intern("x");
+    assert_eq!(x.inline_str(), Some("x"));
+    assert_eq!(interner.resolve_ref(&x), Some("x"));
+    assert!(interner.is_empty());
+}
@@ -0,0 +1,357 @@
+#![cfg(feature = "backends")]
+
+use super::{
+    inline_symbol,
+    resolve_inline_symbol,
+    Backend,
+};
+use crate::{
+    compat::{
+        Arc,
+        Box,
+        Vec,
+    },
+    symbol::{
+        expect_valid_symbol,
+        try_valid_symbol,
+    },
+    BackendMemoryStats,
+    ByteStr,
+    Interner,
+    InternerError,
+    Resolver,
+    Symbol,
+};
+use core::{
+    convert::Infallible,
+    hash::BuildHasher,
+    iter::Enumerate,
+    marker::PhantomData,
+    mem,
+    slice,
+};
+
+/// A backend that stores every interned string in its own [`Arc`].
+///
+/// This extends the design of the [`SimpleBackend`](`super::SimpleBackend`) so
+/// that resolved strings can be shared beyond the borrow of the interner via
+/// [`Interner::resolve_arc`], and so that strings that already live in an
+/// [`Arc`] are adopted via [`Interner::get_or_intern_arc`] without copying.
+///
+/// # Usage
+///
+/// - **Fill:** Efficiency of filling an empty string interner.
+/// - **Resolve:** Efficiency of interned string look-up given a symbol.
+/// - **Allocations:** The number of allocations performed by the backend.
+/// - **Footprint:** The total heap memory consumed by the backend.
+///
+/// Rating varies between **bad**, **ok** and **good**.
+///
+/// | Scenario    |  Rating  |
+/// |:------------|:--------:|
+/// | Fill        | **bad** |
+/// | Resolve     | **good**   |
+/// | Allocations | **bad** |
+/// | Footprint   | **bad**   |
+/// | Supports `get_or_intern_static` | **no** |
+/// | `Send` + `Sync` | **yes** |
+#[derive(Debug)]
+pub struct ArcBackend<S, T: ?Sized = str> {
+    strings: Vec<Arc<T>>,
+    /// The total length of all interned strings in bytes.
+    string_bytes: usize,
+    symbol_marker: PhantomData<fn() -> S>,
+}
+
+impl<S, T: ?Sized> Default for ArcBackend<S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self {
+            strings: Vec::new(),
+            string_bytes: 0,
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T> ArcBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    /// Pushes the given shared string and returns its symbol.
+    ///
+    /// # Errors
+    ///
+    /// - If the backend ran out of symbols.
+    /// - If memory allocation failed.
+    fn try_push_arc(&mut self, string: Arc<T>) -> Result<S, InternerError> {
+        let symbol = try_valid_symbol(self.strings.len())?;
+        self.strings.try_reserve(1)?;
+        self.string_bytes += string.as_bytes().len();
+        self.strings.push(string);
+        Ok(symbol)
+    }
+
+    /// Pushes the given shared string and returns its symbol.
+    ///
+    /// # Panics
+    ///
+    /// If the backend ran out of symbols.
+    fn push_arc(&mut self, string: Arc<T>) -> S {
+        let symbol = expect_valid_symbol(self.strings.len());
+        self.string_bytes += string.as_bytes().len();
+        self.strings.push(string);
+        symbol
+    }
+
+    /// Returns the shared string for the given symbol if any.
+    #[inline]
+    fn resolve_arc(&self, symbol: S) -> Option<&Arc<T>> {
+        self.strings.get(symbol.to_usize())
+    }
+}
+
+impl<S, T> Backend<S, T> for ArcBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+    for<'a> Arc<T>: From<&'a T>,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            strings: Vec::with_capacity(cap),
+            string_bytes: 0,
+            symbol_marker: Default::default(),
+        }
+    }
+
+    #[inline]
+    fn intern(&mut self, string: &T) -> S {
+        self.push_arc(Arc::from(string))
+    }
+
+    /// Interns the given string and returns its symbol.
+    ///
+    /// # Note
+    ///
+    /// Allocating the [`Arc`] for the string is still infallible and might
+    /// abort the process on memory exhaustion.
+    #[inline]
+    fn try_intern(&mut self, string: &T) -> Result<S, InternerError> {
+        self.try_push_arc(Arc::from(string))
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clear(&mut self) {
+        self.strings.clear();
+        self.string_bytes = 0;
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn reserve(&mut self, additional: usize, _additional_bytes: usize) {
+        // Every string has its own allocation so there is nothing to reserve
+        // for the string contents up front.
+        self.strings.reserve(additional);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn shrink_to_fit(&mut self) {
+        self.strings.shrink_to_fit();
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn capacity(&self) -> usize {
+        self.strings.capacity()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        BackendMemoryStats {
+            string_bytes: self.string_bytes,
+            slack_bytes: 0,
+            index_len: self.strings.len(),
+            index_capacity: self.strings.capacity(),
+            index_bytes: self.strings.capacity() * mem::size_of::<Arc<T>>(),
+        }
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        self.resolve_arc(symbol).map(|string| &**string)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        self.strings.get_unchecked(symbol.to_usize())
+    }
+
+    #[inline]
+    fn inline(string: &T) -> Option<S> {
+        inline_symbol(string)
+    }
+
+    #[inline]
+    fn resolve_inline(symbol: &S) -> Option<&T> {
+        resolve_inline_symbol(symbol)
+    }
+}
+
+impl<S, T: ?Sized> Clone for ArcBackend<S, T> {
+    /// Clones the backend sharing the strings with `self`.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            strings: self.strings.clone(),
+            string_bytes: self.string_bytes,
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T> Eq for ArcBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+}
+
+impl<S, T> PartialEq for ArcBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.strings == other.strings
+    }
+}
+
+impl<S, T> Resolver<S, T> for ArcBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+    for<'a> Arc<T>: From<&'a T>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Backend::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(self, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Box::new(Iter::new(self))
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a ArcBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized,
+{
+    type Item = (S, &'a T);
+    type IntoIter = Iter<'a, S, T>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter::new(self)
+    }
+}
+
+pub struct Iter<'a, S, T: ?Sized> {
+    iter: Enumerate<slice::Iter<'a, Arc<T>>>,
+    symbol_marker: PhantomData<fn() -> S>,
+}
+
+impl<'a, S, T: ?Sized> Iter<'a, S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(backend: &'a ArcBackend<S, T>) -> Self {
+        Self {
+            iter: backend.strings.iter().enumerate(),
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<'a, S, T> Iterator for Iter<'a, S, T>
+where
+    S: Symbol,
+    T: ?Sized,
+{
+    type Item = (S, &'a T);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(id, string)| (expect_valid_symbol(id), &**string))
+    }
+}
+
+impl<T, S, H> Interner<T, S, ArcBackend<S, T>, H>
+where
+    T: ?Sized + ByteStr,
+    S: Symbol,
+    H: BuildHasher,
+    for<'a> Arc<T>: From<&'a T>,
+{
+    /// Interns the given shared string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Note
+    ///
+    /// Adopts the allocation of `string` if it has not been interned before
+    /// instead of copying its contents.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern_arc(&mut self, string: Arc<T>) -> S {
+        let shared = Arc::clone(&string);
+        let result = self.try_get_or_intern_using(&*shared, |backend, _| {
+            Ok::<_, Infallible>(backend.push_arc(string))
+        });
+        match result {
+            Ok(symbol) => symbol,
+            Err(infallible) => match infallible {},
+        }
+    }
+
+    /// Returns the shared string for the given symbol if any.
+    ///
+    /// Unlike [`Interner::resolve`] the returned string can outlive the borrow
+    /// of the interner. Symbols that encode their string inline are resolved
+    /// into a new allocation.
+    #[inline]
+    pub fn resolve_arc(&self, symbol: S) -> Option<Arc<T>> {
+        if let Some(string) = <ArcBackend<S, T> as Backend<S, T>>::resolve_inline(&symbol)
+        {
+            return Some(Arc::from(string))
+        }
+        self.backend().resolve_arc(symbol).cloned()
+    }
+}
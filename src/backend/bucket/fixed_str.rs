@@ -1,33 +1,57 @@
 use super::InternedStr;
+use crate::compat::{
+    TryReserveError,
+    Vec,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FixedString {
-    contents: String,
+    contents: Vec<u8>,
 }
 
 impl Default for FixedString {
     #[inline]
     fn default() -> Self {
         Self {
-            contents: String::new(),
+            contents: Vec::new(),
         }
     }
 }
 
+impl From<Vec<u8>> for FixedString {
+    /// Reuses the allocation of the given vector as fixed string.
+    #[inline]
+    fn from(contents: Vec<u8>) -> Self {
+        Self { contents }
+    }
+}
+
 impl FixedString {
     /// Creates a new fixed string with the given fixed capacity.
     #[inline]
     pub fn with_capacity(cap: usize) -> Self {
         Self {
-            contents: String::with_capacity(cap),
+            contents: Vec::with_capacity(cap),
         }
     }
 
-    /// Returns the underlying [`Box<str>`].
+    /// Creates a new fixed string with the given fixed capacity.
+    ///
+    /// # Errors
+    ///
+    /// If the allocation of the fixed capacity failed.
+    #[inline]
+    pub fn try_with_capacity(cap: usize) -> Result<Self, TryReserveError> {
+        let mut contents = Vec::new();
+        contents.try_reserve_exact(cap)?;
+        Ok(Self { contents })
+    }
+
+    /// Returns the underlying [`Vec<u8>`].
     ///
     /// Guarantees not to perform any reallocations in this process.
     #[inline]
-    pub fn finish(self) -> String {
+    pub fn finish(self) -> Vec<u8> {
         self.contents
     }
 
@@ -43,26 +67,24 @@ impl FixedString {
         self.contents.len()
     }
 
-    /// Pushes the given string into the fixed string if there is enough capacity.
+    /// Removes all contents of the fixed string while keeping its capacity.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.contents.clear();
+    }
+
+    /// Pushes the given bytes into the fixed string if there is enough capacity.
     ///
-    /// Returns a reference to the pushed string if there was enough capacity to
+    /// Returns a reference to the pushed bytes if there was enough capacity to
     /// perform the operation. Otherwise returns `None`.
     #[inline]
-    pub fn push_str(&mut self, string: &str) -> Option<InternedStr> {
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> Option<InternedStr> {
         let len = self.len();
-        if self.capacity() < len + string.len() {
+        if self.capacity() < len + bytes.len() {
             return None
         }
-        self.contents.push_str(string);
-        debug_assert_eq!(self.contents.len(), len + string.len());
-        Some(InternedStr::new(
-            // SAFETY: We convert from bytes to utf8 from which we know through the
-            //         input string that they must represent valid utf8.
-            unsafe {
-                core::str::from_utf8_unchecked(
-                    &self.contents.as_bytes()[len..len + string.len()],
-                )
-            },
-        ))
+        self.contents.extend_from_slice(bytes);
+        debug_assert_eq!(self.contents.len(), len + bytes.len());
+        Some(InternedStr::new(&self.contents[len..len + bytes.len()]))
     }
 }
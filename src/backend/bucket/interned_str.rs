@@ -2,33 +2,33 @@
 
 use core::ptr::NonNull;
 
-/// Reference to an interned string.
+/// Reference to the bytes of an interned string.
 ///
 /// It is inherently `unsafe` to use instances of this type and should not be
 /// done outside of the `string-interner` crate itself.
 #[derive(Debug)]
 #[repr(transparent)]
 pub struct InternedStr {
-    ptr: NonNull<str>,
+    ptr: NonNull<[u8]>,
 }
 
 impl InternedStr {
-    /// Creates a new interned string from the given `str`.
+    /// Creates a new interned string from the given bytes.
     #[inline]
-    pub fn new(val: &str) -> Self {
+    pub fn new(val: &[u8]) -> Self {
         InternedStr {
             ptr: NonNull::from(val),
         }
     }
 
-    /// Returns a shared reference to the underlying string.
+    /// Returns a shared reference to the underlying bytes.
     ///
     /// # Safety
     ///
     /// The user has to make sure that no lifetime guarantees are invalidated.
     #[inline]
-    pub(super) fn as_str(&self) -> &str {
-        // SAFETY: This is safe since we only ever operate on interned `str`
+    pub(super) fn as_bytes(&self) -> &[u8] {
+        // SAFETY: This is safe since we only ever operate on interned bytes
         //         that are never moved around in memory to avoid danling
         //         references.
         unsafe { self.ptr.as_ref() }
@@ -40,7 +40,7 @@ impl Eq for InternedStr {}
 impl PartialEq for InternedStr {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.as_str() == other.as_str()
+        self.as_bytes() == other.as_bytes()
     }
 }
 
@@ -51,6 +51,6 @@ mod tests {
     #[test]
     fn size_of() {
         use std::mem;
-        assert_eq!(mem::size_of::<InternedStr>(), mem::size_of::<&str>());
+        assert_eq!(mem::size_of::<InternedStr>(), mem::size_of::<&[u8]>());
     }
 }
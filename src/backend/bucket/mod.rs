@@ -7,15 +7,30 @@ use self::{
     fixed_str::FixedString,
     interned_str::InternedStr,
 };
-use super::Backend;
+use super::{
+    inline_symbol,
+    resolve_inline_symbol,
+    Backend,
+};
 use crate::{
-    compat::Vec,
-    symbol::expect_valid_symbol,
+    compat::{
+        Box,
+        Vec,
+    },
+    symbol::{
+        expect_valid_symbol,
+        try_valid_symbol,
+    },
+    BackendMemoryStats,
+    ByteStr,
+    InternerError,
+    Resolver,
     Symbol,
 };
 use core::{
     iter::Enumerate,
     marker::PhantomData,
+    mem,
     slice,
 };
 
@@ -44,11 +59,13 @@ use core::{
 /// | Supports `get_or_intern_static` | **yes** |
 /// | `Send` + `Sync` | **yes** |
 #[derive(Debug)]
-pub struct BucketBackend<S> {
+pub struct BucketBackend<S, T: ?Sized = str> {
     spans: Vec<InternedStr>,
     head: FixedString,
-    full: Vec<String>,
-    marker: PhantomData<fn() -> S>,
+    full: Vec<Vec<u8>>,
+    /// Empty buckets kept by [`Backend::clear`] for reuse as `head`.
+    free: Vec<Vec<u8>>,
+    marker: PhantomData<fn() -> (S, *const T)>,
 }
 
 /// # Safety
@@ -56,30 +73,32 @@ pub struct BucketBackend<S> {
 /// The bucket backend requires a manual [`Send`] impl because it is self
 /// referential. When cloning a bucket backend a deep clone is performed and
 /// all references to itself are updated for the clone.
-unsafe impl<S> Send for BucketBackend<S> where S: Symbol {}
+unsafe impl<S, T: ?Sized> Send for BucketBackend<S, T> where S: Symbol {}
 
 /// # Safety
 ///
 /// The bucket backend requires a manual [`Send`] impl because it is self
 /// referential. Those references won't escape its own scope and also
 /// the bucket backend has no interior mutability.
-unsafe impl<S> Sync for BucketBackend<S> where S: Symbol {}
+unsafe impl<S, T: ?Sized> Sync for BucketBackend<S, T> where S: Symbol {}
 
-impl<S> Default for BucketBackend<S> {
+impl<S, T: ?Sized> Default for BucketBackend<S, T> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn default() -> Self {
         Self {
             spans: Vec::new(),
             head: FixedString::default(),
             full: Vec::new(),
+            free: Vec::new(),
             marker: Default::default(),
         }
     }
 }
 
-impl<S> Backend<S> for BucketBackend<S>
+impl<S, T> Backend<S, T> for BucketBackend<S, T>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
 {
     #[cfg_attr(feature = "inline-more", inline)]
     fn with_capacity(cap: usize) -> Self {
@@ -87,39 +106,146 @@ where
             spans: Vec::with_capacity(cap),
             head: FixedString::with_capacity(cap),
             full: Vec::new(),
+            free: Vec::new(),
             marker: Default::default(),
         }
     }
 
     #[inline]
-    fn intern(&mut self, string: &str) -> S {
+    fn intern(&mut self, string: &T) -> S {
         // SAFETY: This is safe because we never hand out the returned
         //         interned string instance to the outside and only operate
         //         on it within this backend.
-        let interned = unsafe { self.alloc(string) };
+        let interned = unsafe { self.alloc(string.as_bytes()) };
         self.push_span(interned)
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
-    fn intern_static(&mut self, string: &'static str) -> S {
-        let interned = InternedStr::new(string);
+    fn intern_static(&mut self, string: &'static T) -> S {
+        let interned = InternedStr::new(string.as_bytes());
         self.push_span(interned)
     }
 
     #[inline]
-    fn resolve(&self, symbol: S) -> Option<&str> {
-        self.spans
-            .get(symbol.to_usize())
-            .map(|interned| interned.as_str())
+    fn try_intern(&mut self, string: &T) -> Result<S, InternerError> {
+        let symbol = self.try_reserve_span()?;
+        // SAFETY: This is safe because we never hand out the returned
+        //         interned string instance to the outside and only operate
+        //         on it within this backend.
+        let interned = unsafe { self.try_alloc(string.as_bytes())? };
+        self.spans.push(interned);
+        Ok(symbol)
+    }
+
+    #[inline]
+    fn try_intern_static(&mut self, string: &'static T) -> Result<S, InternerError> {
+        let symbol = self.try_reserve_span()?;
+        self.spans.push(InternedStr::new(string.as_bytes()));
+        Ok(symbol)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clear(&mut self) {
+        self.spans.clear();
+        // Keeps all buckets so that refilling the backend does not allocate.
+        // The free list is in reverse allocation order so that a refill
+        // reuses the buckets in the order they were originally filled.
+        self.head.clear();
+        let old_head = mem::take(&mut self.head).finish();
+        if old_head.capacity() > 0 {
+            self.free.push(old_head);
+        }
+        self.free
+            .extend(self.full.drain(..).rev().map(|mut bucket| {
+                bucket.clear();
+                bucket
+            }));
+        if let Some(bucket) = self.free.pop() {
+            self.head = FixedString::from(bucket);
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn reserve(&mut self, additional: usize, additional_bytes: usize) {
+        self.spans.reserve(additional);
+        let cap = self.head.capacity();
+        if cap - self.head.len() < additional_bytes {
+            let new_head = self.take_free(additional_bytes).unwrap_or_else(|| {
+                let new_cap = usize::max(cap, additional_bytes).next_power_of_two();
+                FixedString::with_capacity(new_cap)
+            });
+            let old_head = core::mem::replace(&mut self.head, new_head);
+            if old_head.len() > 0 {
+                self.full.push(old_head.finish());
+            } else if old_head.capacity() > 0 {
+                self.free.push(old_head.finish());
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn shrink_to_fit(&mut self) {
+        // Buckets cannot be shrunk since spans point into them. However, an
+        // empty head bucket is not referenced by any span and can be freed.
+        self.spans.shrink_to_fit();
+        self.full.shrink_to_fit();
+        self.free = Vec::new();
+        if self.head.len() == 0 {
+            self.head = FixedString::default();
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn capacity(&self) -> usize {
+        self.spans.capacity()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        let (full_len, full_capacity) =
+            self.full.iter().fold((0, 0), |(len, cap), bucket| {
+                (len + bucket.len(), cap + bucket.capacity())
+            });
+        let free_capacity = self.free.iter().map(Vec::capacity).sum::<usize>();
+        let string_bytes = self.head.len() + full_len;
+        let capacity = self.head.capacity() + full_capacity + free_capacity;
+        BackendMemoryStats {
+            string_bytes,
+            slack_bytes: capacity - string_bytes,
+            index_len: self.spans.len(),
+            index_capacity: self.spans.capacity(),
+            index_bytes: self.spans.capacity() * mem::size_of::<InternedStr>()
+                + (self.full.capacity() + self.free.capacity())
+                    * mem::size_of::<Vec<u8>>(),
+        }
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        self.spans.get(symbol.to_usize()).map(|interned| {
+            // SAFETY: This is safe because all interned bytes originate from
+            //         values of type `T`.
+            unsafe { T::from_bytes_unchecked(interned.as_bytes()) }
+        })
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        T::from_bytes_unchecked(self.spans.get_unchecked(symbol.to_usize()).as_bytes())
     }
 
     #[inline]
-    unsafe fn resolve_unchecked(&self, symbol: S) -> &str {
-        self.spans.get_unchecked(symbol.to_usize()).as_str()
+    fn inline(string: &T) -> Option<S> {
+        inline_symbol(string)
+    }
+
+    #[inline]
+    fn resolve_inline(symbol: &S) -> Option<&T> {
+        resolve_inline_symbol(symbol)
     }
 }
 
-impl<S> BucketBackend<S>
+impl<S, T: ?Sized> BucketBackend<S, T>
 where
     S: Symbol,
 {
@@ -135,22 +261,67 @@ where
         symbol
     }
 
+    /// Reserves space for one more span and returns the symbol it will get.
+    fn try_reserve_span(&mut self) -> Result<S, InternerError> {
+        let symbol = try_valid_symbol(self.spans.len())?;
+        self.spans.try_reserve(1)?;
+        Ok(symbol)
+    }
+
+    /// Takes the next free bucket that has a capacity of at least `min_cap` bytes.
+    fn take_free(&mut self, min_cap: usize) -> Option<FixedString> {
+        let index = self
+            .free
+            .iter()
+            .rposition(|bucket| bucket.capacity() >= min_cap)?;
+        Some(FixedString::from(self.free.remove(index)))
+    }
+
+    /// Interns a new string into the backend and returns a reference to it.
+    ///
+    /// # Errors
+    ///
+    /// If a new bucket is required and cannot be allocated.
+    unsafe fn try_alloc(&mut self, string: &[u8]) -> Result<InternedStr, InternerError> {
+        let cap = self.head.capacity();
+        if cap < self.head.len() + string.len() {
+            self.full.try_reserve(1)?;
+            let new_head = match self.take_free(string.len()) {
+                Some(bucket) => bucket,
+                None => {
+                    let new_cap = (usize::max(cap, string.len()) + 1)
+                        .checked_next_power_of_two()
+                        .ok_or(InternerError::StorageOverflow)?;
+                    FixedString::try_with_capacity(new_cap)?
+                }
+            };
+            let old_head = core::mem::replace(&mut self.head, new_head);
+            self.full.push(old_head.finish());
+        }
+        Ok(self
+            .head
+            .push_bytes(string)
+            .expect("encountered invalid head capacity (2)"))
+    }
+
     /// Interns a new string into the backend and returns a reference to it.
-    unsafe fn alloc(&mut self, string: &str) -> InternedStr {
+    unsafe fn alloc(&mut self, string: &[u8]) -> InternedStr {
         let cap = self.head.capacity();
         if cap < self.head.len() + string.len() {
-            let new_cap = (usize::max(cap, string.len()) + 1).next_power_of_two();
-            let new_head = FixedString::with_capacity(new_cap);
+            let new_head = self.take_free(string.len()).unwrap_or_else(|| {
+                let new_cap = (usize::max(cap, string.len()) + 1).next_power_of_two();
+                FixedString::with_capacity(new_cap)
+            });
             let old_head = core::mem::replace(&mut self.head, new_head);
             self.full.push(old_head.finish());
         }
         self.head
-            .push_str(string)
+            .push_bytes(string)
             .expect("encountered invalid head capacity (2)")
     }
 }
 
-impl<S> Clone for BucketBackend<S> {
+impl<S, T: ?Sized> Clone for BucketBackend<S, T> {
     fn clone(&self) -> Self {
         // For performance reasons we copy all cloned strings into a single cloned
         // head string leaving the cloned `full` empty.
@@ -159,9 +330,9 @@ impl<S> Clone for BucketBackend<S> {
         let mut head = FixedString::with_capacity(new_head_cap);
         let mut spans = Vec::with_capacity(self.spans.len());
         for span in &self.spans {
-            let string = span.as_str();
+            let string = span.as_bytes();
             let interned = head
-                .push_str(string)
+                .push_bytes(string)
                 .expect("encountered invalid head capacity");
             spans.push(interned);
         }
@@ -169,14 +340,15 @@ impl<S> Clone for BucketBackend<S> {
             spans,
             head,
             full: Vec::new(),
+            free: Vec::new(),
             marker: Default::default(),
         }
     }
 }
 
-impl<S> Eq for BucketBackend<S> where S: Symbol {}
+impl<S, T: ?Sized> Eq for BucketBackend<S, T> where S: Symbol {}
 
-impl<S> PartialEq for BucketBackend<S>
+impl<S, T: ?Sized> PartialEq for BucketBackend<S, T>
 where
     S: Symbol,
 {
@@ -186,12 +358,44 @@ where
     }
 }
 
-impl<'a, S> IntoIterator for &'a BucketBackend<S>
+impl<S, T> Resolver<S, T> for BucketBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Backend::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(self, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Box::new(Iter::new(self))
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a BucketBackend<S, T>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
 {
-    type Item = (S, &'a str);
-    type IntoIter = Iter<'a, S>;
+    type Item = (S, &'a T);
+    type IntoIter = Iter<'a, S, T>;
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_iter(self) -> Self::IntoIter {
@@ -199,14 +403,14 @@ where
     }
 }
 
-pub struct Iter<'a, S> {
+pub struct Iter<'a, S, T: ?Sized> {
     iter: Enumerate<slice::Iter<'a, InternedStr>>,
-    symbol_marker: PhantomData<fn() -> S>,
+    symbol_marker: PhantomData<fn() -> (S, &'a T)>,
 }
 
-impl<'a, S> Iter<'a, S> {
+impl<'a, S, T: ?Sized> Iter<'a, S, T> {
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn new(backend: &'a BucketBackend<S>) -> Self {
+    pub fn new(backend: &'a BucketBackend<S, T>) -> Self {
         Self {
             iter: backend.spans.iter().enumerate(),
             symbol_marker: Default::default(),
@@ -214,11 +418,12 @@ impl<'a, S> Iter<'a, S> {
     }
 }
 
-impl<'a, S> Iterator for Iter<'a, S>
+impl<'a, S, T> Iterator for Iter<'a, S, T>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
 {
-    type Item = (S, &'a str);
+    type Item = (S, &'a T);
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -227,8 +432,11 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|(id, interned)| (expect_valid_symbol(id), interned.as_str()))
+        self.iter.next().map(|(id, interned)| {
+            // SAFETY: This is safe because all interned bytes originate from
+            //         values of type `T`.
+            let string = unsafe { T::from_bytes_unchecked(interned.as_bytes()) };
+            (expect_valid_symbol(id), string)
+        })
     }
 }
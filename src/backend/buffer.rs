@@ -0,0 +1,399 @@
+#![cfg(feature = "backends")]
+
+use super::{
+    inline_symbol,
+    resolve_inline_symbol,
+    Backend,
+};
+use crate::{
+    compat::{
+        Box,
+        Vec,
+    },
+    symbol::{
+        expect_valid_symbol,
+        try_valid_symbol,
+    },
+    BackendMemoryStats,
+    ByteStr,
+    InternerError,
+    Resolver,
+    Symbol,
+};
+use core::marker::PhantomData;
+
+/// An interner backend that stores all strings in a single buffer with a
+/// length prefix each.
+///
+/// The length of every string is encoded as a variable length LEB128 prefix so
+/// that strings shorter than 128 bytes only need a single additional byte.
+/// Symbols are byte offsets into the buffer and thus exhaust faster than the
+/// symbols of other backends.
+///
+/// # Note
+///
+/// The backend keeps no data besides the buffer. A symbol that has not been
+/// handed out by the backend may point into the middle of a string, so
+/// [`Backend::resolve`] bounds checks the length prefix and the string it
+/// decodes and validates the string bytes via [`ByteStr::from_bytes`], e.g.
+/// as UTF-8 for [`str`]. Such a symbol thus resolves to `None` or to some
+/// valid string, but never to invalid bytes.
+///
+/// # Usage
+///
+/// - **Fill:** Efficiency of filling an empty string interner.
+/// - **Resolve:** Efficiency of interned string look-up given a symbol.
+/// - **Allocations:** The number of allocations performed by the backend.
+/// - **Footprint:** The total heap memory consumed by the backend.
+///
+/// Rating varies between **bad**, **ok** and **good**.
+///
+/// | Scenario    |  Rating  |
+/// |:------------|:--------:|
+/// | Fill        | **good** |
+/// | Resolve     | **ok**   |
+/// | Allocations | **good** |
+/// | Footprint   | **good** |
+/// | Supports `get_or_intern_static` | **no** |
+/// | `Send` + `Sync` | **yes** |
+#[derive(Debug)]
+pub struct BufferBackend<S, T: ?Sized = str> {
+    /// The length prefixed strings.
+    buffer: Vec<u8>,
+    /// The number of interned strings.
+    len: usize,
+    /// The total length of all interned strings in bytes without prefixes.
+    string_bytes: usize,
+    marker: PhantomData<fn() -> (S, *const T)>,
+}
+
+impl<S, T: ?Sized> Default for BufferBackend<S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self {
+            buffer: Vec::new(),
+            len: 0,
+            string_bytes: 0,
+            marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T: ?Sized> Clone for BufferBackend<S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            len: self.len,
+            string_bytes: self.string_bytes,
+            marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T: ?Sized> PartialEq for BufferBackend<S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.buffer == other.buffer
+    }
+}
+
+impl<S, T: ?Sized> Eq for BufferBackend<S, T> {}
+
+/// Returns the number of bytes of the LEB128 encoding of `value`.
+#[inline]
+fn encoded_len(value: usize) -> usize {
+    let bits = (usize::BITS - value.leading_zeros()) as usize;
+    usize::max(1, bits.div_ceil(7))
+}
+
+/// Appends the LEB128 encoding of `value` to the buffer.
+#[inline]
+fn encode(buffer: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Decodes the LEB128 encoded value at the start of `bytes`.
+///
+/// Returns the value and the number of bytes of its encoding, or `None` if
+/// `bytes` do not start with a complete encoding of a `usize`.
+#[inline]
+fn decode(bytes: &[u8]) -> Option<(usize, usize)> {
+    let mut value = 0_usize;
+    for (index, &byte) in bytes.iter().enumerate() {
+        let shift = 7 * index as u32;
+        let bits = (byte & 0x7F) as usize;
+        if shift >= usize::BITS || (bits << shift) >> shift != bits {
+            return None
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Some((value, index + 1))
+        }
+    }
+    None
+}
+
+/// Decodes the LEB128 encoded value at the start of `bytes`.
+///
+/// Returns the value and the number of bytes of its encoding.
+///
+/// # Safety
+///
+/// The bytes must start with a value encoded by [`encode`].
+#[inline]
+unsafe fn decode_unchecked(bytes: &[u8]) -> (usize, usize) {
+    let mut value = 0;
+    let mut index = 0;
+    loop {
+        let byte = *bytes.get_unchecked(index);
+        value |= ((byte & 0x7F) as usize) << (7 * index);
+        index += 1;
+        if byte & 0x80 == 0 {
+            return (value, index)
+        }
+    }
+}
+
+impl<S, T> BufferBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    /// Returns the string starting at the given buffer offset if any.
+    ///
+    /// Bounds checks the length prefix and the string and validates the string
+    /// bytes so that offsets into the middle of a string never yield bytes that
+    /// are invalid for `T`.
+    #[inline]
+    fn checked_string_at(&self, offset: usize) -> Option<&T> {
+        let (len, prefix_len) = decode(self.buffer.get(offset..)?)?;
+        let start = offset + prefix_len;
+        let bytes = self.buffer.get(start..start.checked_add(len)?)?;
+        T::from_bytes(bytes)
+    }
+
+    /// Returns the string starting at the given buffer offset and the offset
+    /// of the next string.
+    ///
+    /// # Safety
+    ///
+    /// A string must start at the given offset.
+    #[inline]
+    unsafe fn string_at(&self, offset: usize) -> (&T, usize) {
+        let (len, prefix_len) = decode_unchecked(self.buffer.get_unchecked(offset..));
+        let start = offset + prefix_len;
+        let end = start + len;
+        let string = T::from_bytes_unchecked(self.buffer.get_unchecked(start..end));
+        (string, end)
+    }
+
+    /// Pushes the given string into the buffer and returns its symbol.
+    ///
+    /// # Errors
+    ///
+    /// - If the backend ran out of symbols.
+    /// - If memory allocation failed.
+    fn try_push_string(&mut self, string: &T) -> Result<S, InternerError> {
+        let offset = self.buffer.len();
+        let symbol = try_valid_symbol(offset)?;
+        let string = string.as_bytes();
+        let additional = encoded_len(string.len())
+            .checked_add(string.len())
+            .ok_or(InternerError::StorageOverflow)?;
+        self.buffer.try_reserve(additional)?;
+        encode(&mut self.buffer, string.len());
+        self.buffer.extend_from_slice(string);
+        self.len += 1;
+        self.string_bytes += string.len();
+        Ok(symbol)
+    }
+}
+
+impl<S, T> Backend<S, T> for BufferBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity(cap: usize) -> Self {
+        // According to google the approx. word length is 5 plus the prefix.
+        let default_word_len = 6;
+        Self {
+            buffer: Vec::with_capacity(cap * default_word_len),
+            len: 0,
+            string_bytes: 0,
+            marker: Default::default(),
+        }
+    }
+
+    #[inline]
+    fn intern(&mut self, string: &T) -> S {
+        match self.try_intern(string) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    #[inline]
+    fn try_intern(&mut self, string: &T) -> Result<S, InternerError> {
+        self.try_push_string(string)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clear(&mut self) {
+        self.buffer.clear();
+        self.len = 0;
+        self.string_bytes = 0;
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn reserve(&mut self, additional: usize, additional_bytes: usize) {
+        // Most strings are shorter than 128 bytes and need a single byte prefix.
+        let additional = additional + additional_bytes;
+        self.buffer.reserve(additional);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn shrink_to_fit(&mut self) {
+        self.buffer.shrink_to_fit();
+    }
+
+    fn extend_from(&mut self, other: &Self) -> Option<usize> {
+        let shift = self.buffer.len();
+        if other.buffer.is_empty() {
+            return Some(shift)
+        }
+        // Every symbol of `other` is an offset below the length of its buffer.
+        S::try_from_usize(shift.checked_add(other.buffer.len() - 1)?)?;
+        self.buffer.extend_from_slice(&other.buffer);
+        self.len += other.len;
+        self.string_bytes += other.string_bytes;
+        Some(shift)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        BackendMemoryStats {
+            string_bytes: self.string_bytes,
+            slack_bytes: self.buffer.capacity() - self.buffer.len(),
+            index_len: self.len,
+            index_capacity: self.len,
+            index_bytes: self.buffer.len() - self.string_bytes,
+        }
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        self.checked_string_at(symbol.to_usize())
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        self.string_at(symbol.to_usize()).0
+    }
+
+    #[inline]
+    fn inline(string: &T) -> Option<S> {
+        inline_symbol(string)
+    }
+
+    #[inline]
+    fn resolve_inline(symbol: &S) -> Option<&T> {
+        resolve_inline_symbol(symbol)
+    }
+}
+
+impl<S, T> Resolver<S, T> for BufferBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Backend::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(self, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Box::new(Iter::new(self))
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a BufferBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    type Item = (S, &'a T);
+    type IntoIter = Iter<'a, S, T>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter::new(self)
+    }
+}
+
+pub struct Iter<'a, S, T: ?Sized> {
+    backend: &'a BufferBackend<S, T>,
+    /// The offset of the next string.
+    offset: usize,
+    /// The number of remaining strings.
+    remaining: usize,
+}
+
+impl<'a, S, T: ?Sized> Iter<'a, S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(backend: &'a BufferBackend<S, T>) -> Self {
+        Self {
+            backend,
+            offset: 0,
+            remaining: backend.len,
+        }
+    }
+}
+
+impl<'a, S, T> Iterator for Iter<'a, S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    type Item = (S, &'a T);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let offset = self.offset;
+        // SAFETY: All strings are stored back to back so that the next string
+        //         starts where the previous one ends.
+        let (string, next) = unsafe { self.backend.string_at(offset) };
+        self.offset = next;
+        self.remaining -= 1;
+        Some((expect_valid_symbol(offset), string))
+    }
+}
@@ -0,0 +1,414 @@
+#![cfg(feature = "backends")]
+
+use super::{
+    inline_symbol,
+    resolve_inline_symbol,
+    Backend,
+    RemovableBackend,
+};
+use crate::{
+    compat::{
+        Box,
+        Vec,
+    },
+    symbol::try_valid_symbol,
+    BackendMemoryStats,
+    ByteStr,
+    InternerError,
+    Resolver,
+    Symbol,
+};
+use core::{
+    iter::Enumerate,
+    marker::PhantomData,
+    mem,
+    slice,
+};
+
+/// The number of low bits of a symbol that encode the slot index.
+///
+/// The remaining high bits encode the generation of the slot.
+const INDEX_BITS: u32 = 24;
+
+/// Mask to extract the slot index of a symbol.
+const INDEX_MASK: usize = (1 << INDEX_BITS) - 1;
+
+/// Packs the slot index and generation into the `usize` representation of a symbol.
+#[inline]
+fn pack(index: usize, generation: u8) -> usize {
+    (usize::from(generation) << INDEX_BITS) | index
+}
+
+/// Unpacks the slot index and generation from the `usize` representation of a symbol.
+///
+/// Returns `None` if the value cannot have been created by [`pack`].
+#[inline]
+fn unpack(value: usize) -> Option<(usize, u8)> {
+    let generation = value >> INDEX_BITS;
+    if generation > usize::from(u8::MAX) {
+        return None
+    }
+    Some((value & INDEX_MASK, generation as u8))
+}
+
+/// A backend that supports removing interned strings.
+///
+/// Every interned string is stored in its own allocation within a slot.
+/// Removing a string frees its allocation and puts its slot onto a free list
+/// so that it is reused by the next interned string.
+///
+/// Symbols encode the slot index in their low 24 bits and the generation of the
+/// slot in the 8 bits above. The generation is bumped whenever a string is removed
+/// so that resolving a stale symbol returns `None` instead of a different string.
+/// Slots whose generation is exhausted are retired and never reused.
+///
+/// # Note
+///
+/// This backend supports at most `2^24` slots. Symbols of reused slots require
+/// at least 32 bits, so with [`SymbolU16`](`crate::symbol::SymbolU16`) freed
+/// slots are retired instead of reused.
+///
+/// Symbols are only dense as long as no strings have been removed.
+///
+/// # Usage
+///
+/// - **Fill:** Efficiency of filling an empty string interner.
+/// - **Resolve:** Efficiency of interned string look-up given a symbol.
+/// - **Allocations:** The number of allocations performed by the backend.
+/// - **Footprint:** The total heap memory consumed by the backend.
+///
+/// Rating varies between **bad**, **ok** and **good**.
+///
+/// | Scenario    |  Rating  |
+/// |:------------|:--------:|
+/// | Fill        | **bad** |
+/// | Resolve     | **good**   |
+/// | Allocations | **bad** |
+/// | Footprint   | **bad**   |
+/// | Supports `get_or_intern_static` | **no** |
+/// | Supports `remove` | **yes** |
+/// | `Send` + `Sync` | **yes** |
+#[derive(Debug)]
+pub struct FreeListBackend<S, T: ?Sized = str> {
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+    /// The number of live strings.
+    len: usize,
+    /// The total length of all live strings in bytes.
+    string_bytes: usize,
+    symbol_marker: PhantomData<fn() -> (S, *const T)>,
+}
+
+/// A slot of the [`FreeListBackend`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Slot {
+    /// The generation of the slot, bumped whenever its string is removed.
+    generation: u8,
+    /// The interned string or `None` if the slot is free or retired.
+    string: Option<Box<[u8]>>,
+}
+
+impl<S, T: ?Sized> Default for FreeListBackend<S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            string_bytes: 0,
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T: ?Sized> FreeListBackend<S, T>
+where
+    S: Symbol,
+{
+    /// Pops the next free slot that can be represented by a symbol.
+    ///
+    /// Free slots that cannot be represented by `S` are retired.
+    fn pop_free(&mut self) -> Option<(usize, S)> {
+        while let Some(index) = self.free.pop() {
+            let generation = self.slots[index].generation;
+            if let Some(symbol) = S::try_from_usize(pack(index, generation)) {
+                return Some((index, symbol))
+            }
+        }
+        None
+    }
+
+    /// Removes the string of the slot at `index` if it is live.
+    ///
+    /// Returns `true` if the slot was live.
+    fn remove_at(&mut self, index: usize) -> bool {
+        let slot = &mut self.slots[index];
+        match slot.string.take() {
+            Some(string) => self.string_bytes -= string.len(),
+            None => return false,
+        }
+        self.len -= 1;
+        if let Some(generation) = slot.generation.checked_add(1) {
+            slot.generation = generation;
+            self.free.push(index);
+        }
+        true
+    }
+
+    /// Returns the slot of a live string for the given symbol if any.
+    #[inline]
+    fn live_slot(&self, symbol: S) -> Option<&[u8]> {
+        let (index, generation) = unpack(symbol.to_usize())?;
+        let slot = self.slots.get(index)?;
+        if slot.generation != generation {
+            return None
+        }
+        slot.string.as_deref()
+    }
+}
+
+impl<S, T> Backend<S, T> for FreeListBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(cap),
+            free: Vec::new(),
+            len: 0,
+            string_bytes: 0,
+            symbol_marker: Default::default(),
+        }
+    }
+
+    #[inline]
+    fn intern(&mut self, string: &T) -> S {
+        match self.try_intern(string) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    #[inline]
+    fn try_intern(&mut self, string: &T) -> Result<S, InternerError> {
+        let string = string.as_bytes();
+        let mut str = Vec::new();
+        str.try_reserve_exact(string.len())?;
+        str.extend_from_slice(string);
+        let str = Some(str.into_boxed_slice());
+        if let Some((index, symbol)) = self.pop_free() {
+            self.slots[index].string = str;
+            self.len += 1;
+            self.string_bytes += string.len();
+            return Ok(symbol)
+        }
+        let index = self.slots.len();
+        if index > INDEX_MASK {
+            return Err(InternerError::SymbolsExhausted)
+        }
+        let symbol = try_valid_symbol(index)?;
+        self.slots.try_reserve(1)?;
+        self.slots.push(Slot {
+            generation: 0,
+            string: str,
+        });
+        self.len += 1;
+        self.string_bytes += string.len();
+        Ok(symbol)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clear(&mut self) {
+        // Removes all strings one by one instead of dropping the slots so that
+        // symbols handed out before clearing never resolve again.
+        for index in 0..self.slots.len() {
+            self.remove_at(index);
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn reserve(&mut self, additional: usize, _additional_bytes: usize) {
+        self.slots
+            .reserve(additional.saturating_sub(self.free.len()));
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn shrink_to_fit(&mut self) {
+        self.slots.shrink_to_fit();
+        self.free.shrink_to_fit();
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        BackendMemoryStats {
+            string_bytes: self.string_bytes,
+            slack_bytes: 0,
+            index_len: self.slots.len(),
+            index_capacity: self.slots.capacity(),
+            index_bytes: self.slots.capacity() * mem::size_of::<Slot>()
+                + self.free.capacity() * mem::size_of::<usize>(),
+        }
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        self.live_slot(symbol).map(|string| {
+            // SAFETY: This is safe because all interned bytes originate from
+            //         values of type `T`.
+            unsafe { T::from_bytes_unchecked(string) }
+        })
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        let index = symbol.to_usize() & INDEX_MASK;
+        match &self.slots.get_unchecked(index).string {
+            Some(string) => T::from_bytes_unchecked(string),
+            None => core::hint::unreachable_unchecked(),
+        }
+    }
+
+    #[inline]
+    fn inline(string: &T) -> Option<S> {
+        inline_symbol(string)
+    }
+
+    #[inline]
+    fn resolve_inline(symbol: &S) -> Option<&T> {
+        resolve_inline_symbol(symbol)
+    }
+}
+
+impl<S, T> RemovableBackend<S, T> for FreeListBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[inline]
+    fn remove(&mut self, symbol: S) -> bool {
+        if self.live_slot(symbol).is_none() {
+            return false
+        }
+        self.remove_at(symbol.to_usize() & INDEX_MASK)
+    }
+}
+
+impl<S, T: ?Sized> Clone for FreeListBackend<S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+            len: self.len,
+            string_bytes: self.string_bytes,
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T: ?Sized> Eq for FreeListBackend<S, T> where S: Symbol {}
+
+impl<S, T: ?Sized> PartialEq for FreeListBackend<S, T>
+where
+    S: Symbol,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.slots == other.slots
+    }
+}
+
+impl<S, T> Resolver<S, T> for FreeListBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Backend::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(self, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Box::new(Iter::new(self))
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a FreeListBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    type Item = (S, &'a T);
+    type IntoIter = Iter<'a, S, T>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter::new(self)
+    }
+}
+
+pub struct Iter<'a, S, T: ?Sized> {
+    iter: Enumerate<slice::Iter<'a, Slot>>,
+    symbol_marker: PhantomData<fn() -> (S, &'a T)>,
+}
+
+impl<'a, S, T: ?Sized> Iter<'a, S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(backend: &'a FreeListBackend<S, T>) -> Self {
+        Self {
+            iter: backend.slots.iter().enumerate(),
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<'a, S, T> Iterator for Iter<'a, S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    type Item = (S, &'a T);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in &mut self.iter {
+            if let Some(string) = &slot.string {
+                let symbol = S::try_from_usize(pack(index, slot.generation))
+                    .expect("encountered invalid symbol");
+                // SAFETY: This is safe because all interned bytes originate from
+                //         values of type `T`.
+                let string = unsafe { T::from_bytes_unchecked(string) };
+                return Some((symbol, string))
+            }
+        }
+        None
+    }
+}
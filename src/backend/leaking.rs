@@ -0,0 +1,332 @@
+#![cfg(feature = "backends")]
+
+use super::{
+    inline_symbol,
+    resolve_inline_symbol,
+    Backend,
+};
+use crate::{
+    compat::{
+        Box,
+        Vec,
+    },
+    symbol::{
+        expect_valid_symbol,
+        try_valid_symbol,
+    },
+    BackendMemoryStats,
+    ByteStr,
+    InternerError,
+    Resolver,
+    Symbol,
+};
+use core::{
+    iter::Enumerate,
+    marker::PhantomData,
+    mem,
+    slice,
+};
+
+/// A backend that leaks every interned string and never frees it.
+///
+/// Since interned strings are never freed they can be resolved with a `'static`
+/// lifetime via [`LeakingBackend::resolve_static`]. Use this for interners that
+/// live for the entire lifetime of the program, such as the global interner.
+///
+/// # Note
+///
+/// Dropping or clearing this backend does not free any interned strings.
+///
+/// # Usage
+///
+/// - **Fill:** Efficiency of filling an empty string interner.
+/// - **Resolve:** Efficiency of interned string look-up given a symbol.
+/// - **Allocations:** The number of allocations performed by the backend.
+/// - **Footprint:** The total heap memory consumed by the backend.
+///
+/// Rating varies between **bad**, **ok** and **good**.
+///
+/// | Scenario    |  Rating  |
+/// |:------------|:--------:|
+/// | Fill        | **bad** |
+/// | Resolve     | **good**   |
+/// | Allocations | **bad** |
+/// | Footprint   | **bad**   |
+/// | Supports `get_or_intern_static` | **yes** |
+/// | `Send` + `Sync` | **yes** |
+#[derive(Debug)]
+pub struct LeakingBackend<S, T: ?Sized + 'static = str> {
+    strings: Vec<&'static T>,
+    /// The total length of all interned strings in bytes.
+    string_bytes: usize,
+    symbol_marker: PhantomData<fn() -> S>,
+}
+
+impl<S, T: ?Sized> Default for LeakingBackend<S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self {
+            strings: Vec::new(),
+            string_bytes: 0,
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T> LeakingBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    /// Resolves the given symbol to its original string contents.
+    ///
+    /// Unlike [`Backend::resolve`] the returned string outlives the backend.
+    #[inline]
+    pub fn resolve_static(&self, symbol: S) -> Option<&'static T> {
+        self.strings.get(symbol.to_usize()).copied()
+    }
+
+    /// Pushes the given leaked string and returns its symbol.
+    fn push_string(&mut self, string: &'static T) -> S {
+        let symbol = expect_valid_symbol(self.strings.len());
+        self.string_bytes += string.as_bytes().len();
+        self.strings.push(string);
+        symbol
+    }
+
+    /// Pushes the given leaked string and returns its symbol.
+    ///
+    /// # Errors
+    ///
+    /// If the backend ran out of symbols or memory.
+    fn try_push_string(&mut self, string: &'static T) -> Result<S, InternerError> {
+        let symbol = try_valid_symbol(self.strings.len())?;
+        self.strings.try_reserve(1)?;
+        self.string_bytes += string.as_bytes().len();
+        self.strings.push(string);
+        Ok(symbol)
+    }
+
+    /// Leaks a copy of the given string.
+    fn leak(string: &T) -> &'static T {
+        let leaked: &'static [u8] = Box::leak(Box::from(string.as_bytes()));
+        // SAFETY: This is safe because the leaked bytes originate from a value
+        //         of type `T`.
+        unsafe { T::from_bytes_unchecked(leaked) }
+    }
+
+    /// Leaks a copy of the given string.
+    ///
+    /// # Errors
+    ///
+    /// If memory allocation failed.
+    fn try_leak(string: &T) -> Result<&'static T, InternerError> {
+        let string = string.as_bytes();
+        let mut leaked = Vec::new();
+        leaked.try_reserve_exact(string.len())?;
+        leaked.extend_from_slice(string);
+        let leaked: &'static [u8] = Box::leak(leaked.into_boxed_slice());
+        // SAFETY: This is safe because the leaked bytes originate from a value
+        //         of type `T`.
+        Ok(unsafe { T::from_bytes_unchecked(leaked) })
+    }
+}
+
+impl<S, T> Backend<S, T> for LeakingBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            strings: Vec::with_capacity(cap),
+            string_bytes: 0,
+            symbol_marker: Default::default(),
+        }
+    }
+
+    #[inline]
+    fn intern(&mut self, string: &T) -> S {
+        self.push_string(Self::leak(string))
+    }
+
+    #[inline]
+    fn intern_static(&mut self, string: &'static T) -> S {
+        self.push_string(string)
+    }
+
+    #[inline]
+    fn try_intern(&mut self, string: &T) -> Result<S, InternerError> {
+        // Checks for remaining symbols first to not leak the string in vain.
+        try_valid_symbol::<S>(self.strings.len())?;
+        self.try_push_string(Self::try_leak(string)?)
+    }
+
+    #[inline]
+    fn try_intern_static(&mut self, string: &'static T) -> Result<S, InternerError> {
+        self.try_push_string(string)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clear(&mut self) {
+        self.strings.clear();
+        self.string_bytes = 0;
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn reserve(&mut self, additional: usize, _additional_bytes: usize) {
+        self.strings.reserve(additional);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn shrink_to_fit(&mut self) {
+        self.strings.shrink_to_fit();
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn capacity(&self) -> usize {
+        self.strings.capacity()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        BackendMemoryStats {
+            string_bytes: self.string_bytes,
+            slack_bytes: 0,
+            index_len: self.strings.len(),
+            index_capacity: self.strings.capacity(),
+            index_bytes: self.strings.capacity() * mem::size_of::<&'static T>(),
+        }
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        self.resolve_static(symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        self.strings.get_unchecked(symbol.to_usize())
+    }
+
+    #[inline]
+    fn inline(string: &T) -> Option<S> {
+        inline_symbol(string)
+    }
+
+    #[inline]
+    fn resolve_inline(symbol: &S) -> Option<&T> {
+        resolve_inline_symbol(symbol)
+    }
+}
+
+impl<S, T: ?Sized> Clone for LeakingBackend<S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        // The leaked strings are never freed and thus can be shared.
+        Self {
+            strings: self.strings.clone(),
+            string_bytes: self.string_bytes,
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T> Eq for LeakingBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+}
+
+impl<S, T> PartialEq for LeakingBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.strings == other.strings
+    }
+}
+
+impl<S, T> Resolver<S, T> for LeakingBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Backend::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(self, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Box::new(Iter::new(self))
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a LeakingBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    type Item = (S, &'a T);
+    type IntoIter = Iter<'a, S, T>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter::new(self)
+    }
+}
+
+pub struct Iter<'a, S, T: ?Sized + 'static> {
+    iter: Enumerate<slice::Iter<'a, &'static T>>,
+    symbol_marker: PhantomData<fn() -> S>,
+}
+
+impl<'a, S, T: ?Sized> Iter<'a, S, T> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(backend: &'a LeakingBackend<S, T>) -> Self {
+        Self {
+            iter: backend.strings.iter().enumerate(),
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<'a, S, T> Iterator for Iter<'a, S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    type Item = (S, &'a T);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(id, &string)| (expect_valid_symbol(id), string))
+    }
+}
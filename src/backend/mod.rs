@@ -4,29 +4,50 @@
 //! There are trade-offs for the different kinds of backends. A user should
 //! find the backend that suits their use case best.
 
+mod arc;
 mod bucket;
+mod buffer;
+mod free_list;
+mod leaking;
 mod simple;
 mod string;
+mod value;
 
 #[cfg(feature = "backends")]
 pub use self::{
+    arc::ArcBackend,
     bucket::BucketBackend,
+    buffer::BufferBackend,
+    free_list::FreeListBackend,
+    leaking::LeakingBackend,
     simple::SimpleBackend,
-    string::StringBackend,
+    string::{
+        Offset,
+        StringBackend,
+    },
+    value::ValueBackend,
+};
+#[cfg(feature = "backends")]
+use crate::ByteStr;
+use crate::{
+    BackendMemoryStats,
+    InternerError,
+    Symbol,
 };
-use crate::Symbol;
 
 #[cfg(not(feature = "backends"))]
 /// Indicates that no proper backend is in use.
-pub struct NoBackend<S>(core::marker::PhantomData<S>);
+pub struct NoBackend<S, T: ?Sized = str>(
+    core::marker::PhantomData<fn() -> (S, *const T)>,
+);
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "backends")] {
         /// The default backend recommended for general use.
-        pub type DefaultBackend<S> = BucketBackend<S>;
+        pub type DefaultBackend<S, T = str> = BucketBackend<S, T>;
     } else {
         /// The `backends` crate feature is disabled thus there is no default backend.
-        pub type DefaultBackend<S> = NoBackend<S>;
+        pub type DefaultBackend<S, T = str> = NoBackend<S, T>;
     }
 }
 
@@ -35,7 +56,11 @@ cfg_if::cfg_if! {
 /// The job of a backend is to actually store, manage and organize the interned
 /// strings. Different backends have different trade-offs. Users should pick
 /// their backend with hinsight of their personal use-case.
-pub trait Backend<S>: Default
+///
+/// The interned string type `T` is [`str`] by default. Most backends provided by
+/// this crate support all [`ByteStr`](`crate::ByteStr`) types such as `[u8]`
+/// whereas the [`ValueBackend`] supports arbitrary values.
+pub trait Backend<S, T: ?Sized = str>: Default
 where
     S: Symbol,
 {
@@ -50,7 +75,7 @@ where
     ///
     /// The backend must make sure that the returned symbol maps back to the
     /// original string in its [`resolve`](`Backend::resolve`) method.
-    fn intern(&mut self, string: &str) -> S;
+    fn intern(&mut self, string: &T) -> S;
 
     /// Interns the given static string and returns its interned ref and symbol.
     ///
@@ -59,15 +84,113 @@ where
     /// The backend must make sure that the returned symbol maps back to the
     /// original string in its [`resolve`](`Backend::resolve`) method.
     #[inline]
-    fn intern_static(&mut self, string: &'static str) -> S {
+    fn intern_static(&mut self, string: &'static T) -> S {
         // The default implementation simply forwards to the normal [`intern`]
         // implementation. Backends that can optimize for this use case should
         // implement this method.
         self.intern(string)
     }
 
+    /// Interns the given string and returns its symbol.
+    ///
+    /// Unlike [`intern`](`Backend::intern`) this returns an error instead of
+    /// panicking if the backend ran out of symbols, storage or memory.
+    ///
+    /// # Note
+    ///
+    /// The default implementation simply forwards to [`intern`](`Backend::intern`)
+    /// and thus might still panic. Backends should override this method.
+    #[inline]
+    fn try_intern(&mut self, string: &T) -> Result<S, InternerError> {
+        Ok(self.intern(string))
+    }
+
+    /// Interns the given static string and returns its symbol.
+    ///
+    /// Unlike [`intern_static`](`Backend::intern_static`) this returns an error
+    /// instead of panicking if the backend ran out of symbols, storage or memory.
+    #[inline]
+    fn try_intern_static(&mut self, string: &'static T) -> Result<S, InternerError> {
+        // The default implementation simply forwards to the normal [`try_intern`]
+        // implementation. Backends that can optimize for this use case should
+        // implement this method.
+        self.try_intern(string)
+    }
+
+    /// Removes all interned strings from the backend.
+    ///
+    /// Backends should keep their allocations so that they can be reused.
+    ///
+    /// # Note
+    ///
+    /// The default implementation replaces the backend with a new one
+    /// and thus frees all of its allocations.
+    #[inline]
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Reserves capacity for at least `additional` more strings that span
+    /// `additional_bytes` bytes in total.
+    ///
+    /// # Note
+    ///
+    /// The default implementation does nothing.
+    #[inline]
+    fn reserve(&mut self, additional: usize, additional_bytes: usize) {
+        let _ = (additional, additional_bytes);
+    }
+
+    /// Shrinks the capacity of the backend as much as possible.
+    ///
+    /// # Note
+    ///
+    /// The default implementation does nothing.
+    #[inline]
+    fn shrink_to_fit(&mut self) {}
+
+    /// Returns the number of strings the backend can hold without reallocating.
+    ///
+    /// # Note
+    ///
+    /// The default implementation returns `0`.
+    #[inline]
+    fn capacity(&self) -> usize {
+        0
+    }
+
+    /// Returns statistics about the heap memory used by the backend.
+    ///
+    /// Backends should compute these in constant or near-constant time.
+    ///
+    /// # Note
+    ///
+    /// The default implementation reports no memory usage at all.
+    #[inline]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        BackendMemoryStats::default()
+    }
+
+    /// Appends all strings of `other` to the backend in bulk.
+    ///
+    /// Returns the shift `n` such that every symbol `s` handed out by `other`
+    /// afterwards refers to the same string as the symbol with the index
+    /// `s.to_usize() + n` in `self`. Returns `None` and leaves the backend
+    /// unchanged if it cannot append the strings this way.
+    ///
+    /// Used by [`Interner::merge_from_same_backend`](`crate::Interner::merge_from_same_backend`).
+    ///
+    /// # Note
+    ///
+    /// The default implementation returns `None`.
+    #[inline]
+    fn extend_from(&mut self, other: &Self) -> Option<usize> {
+        let _ = other;
+        None
+    }
+
     /// Resolves the given symbol to its original string contents.
-    fn resolve(&self, symbol: S) -> Option<&str>;
+    fn resolve(&self, symbol: S) -> Option<&T>;
 
     /// Resolves the given symbol to its original string contents.
     ///
@@ -78,5 +201,93 @@ where
     /// by the [`intern`](`Backend::intern`) or
     /// [`intern_static`](`Backend::intern_static`) methods of the same
     /// interner backend.
-    unsafe fn resolve_unchecked(&self, symbol: S) -> &str;
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T;
+
+    /// Returns the symbol that encodes the given string inline if any.
+    ///
+    /// Inline strings are never stored by the backend and instead resolved via
+    /// [`resolve_inline`](`Backend::resolve_inline`).
+    ///
+    /// # Note
+    ///
+    /// The default implementation never encodes strings inline.
+    #[inline]
+    fn inline(string: &T) -> Option<S> {
+        let _ = string;
+        None
+    }
+
+    /// Resolves the given symbol if it encodes its string inline.
+    ///
+    /// # Note
+    ///
+    /// The default implementation never resolves symbols inline.
+    #[inline]
+    fn resolve_inline(symbol: &S) -> Option<&T> {
+        let _ = symbol;
+        None
+    }
+
+    /// Resolves the given symbol to its original string contents.
+    ///
+    /// Unlike [`resolve`](`Backend::resolve`) this borrows the symbol and thus
+    /// also resolves symbols that encode their string inline.
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        match Self::resolve_inline(symbol) {
+            Some(string) => Some(string),
+            None => self.resolve(*symbol),
+        }
+    }
+}
+
+/// Returns the symbol that encodes the given byte string inline if any.
+///
+/// Used by the byte based backends to implement [`Backend::inline`].
+#[cfg(feature = "backends")]
+#[inline]
+fn inline_symbol<S, T>(string: &T) -> Option<S>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    let bytes = string.as_bytes();
+    if bytes.len() > S::INLINE_CAPACITY {
+        return None
+    }
+    S::try_inline(core::str::from_utf8(bytes).ok()?)
+}
+
+/// Resolves the given symbol to a byte string if it encodes its string inline.
+///
+/// Used by the byte based backends to implement [`Backend::resolve_inline`].
+#[cfg(feature = "backends")]
+#[inline]
+fn resolve_inline_symbol<S, T>(symbol: &S) -> Option<&T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    symbol.inline_str().map(|string| {
+        // SAFETY: `ByteStr` types accept any valid UTF-8.
+        unsafe { T::from_bytes_unchecked(string.as_bytes()) }
+    })
+}
+
+/// Backends that support removing interned strings.
+///
+/// Used by [`Interner::remove`](`crate::Interner::remove`).
+pub trait RemovableBackend<S, T: ?Sized = str>: Backend<S, T>
+where
+    S: Symbol,
+{
+    /// Removes the string for the given symbol.
+    ///
+    /// Returns `true` if the symbol referred to an interned string.
+    ///
+    /// # Note
+    ///
+    /// Afterwards the backend must never resolve the given symbol again,
+    /// even if it reuses the storage of the removed string.
+    fn remove(&mut self, symbol: S) -> bool;
 }
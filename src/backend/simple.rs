@@ -1,18 +1,29 @@
 #![cfg(feature = "backends")]
 
-use super::Backend;
+use super::{
+    inline_symbol,
+    resolve_inline_symbol,
+    Backend,
+};
 use crate::{
     compat::{
         Box,
-        ToString,
         Vec,
     },
-    symbol::expect_valid_symbol,
+    symbol::{
+        expect_valid_symbol,
+        try_valid_symbol,
+    },
+    BackendMemoryStats,
+    ByteStr,
+    InternerError,
+    Resolver,
     Symbol,
 };
 use core::{
     iter::Enumerate,
     marker::PhantomData,
+    mem,
     slice,
 };
 
@@ -40,65 +51,133 @@ use core::{
 /// | Supports `get_or_intern_static` | **no** |
 /// | `Send` + `Sync` | **yes** |
 #[derive(Debug)]
-pub struct SimpleBackend<S> {
-    strings: Vec<Box<str>>,
-    symbol_marker: PhantomData<fn() -> S>,
+pub struct SimpleBackend<S, T: ?Sized = str> {
+    strings: Vec<Box<[u8]>>,
+    /// The total length of all interned strings in bytes.
+    string_bytes: usize,
+    symbol_marker: PhantomData<fn() -> (S, *const T)>,
 }
 
-impl<S> Default for SimpleBackend<S> {
+impl<S, T: ?Sized> Default for SimpleBackend<S, T> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn default() -> Self {
         Self {
             strings: Vec::new(),
+            string_bytes: 0,
             symbol_marker: Default::default(),
         }
     }
 }
 
-impl<S> Backend<S> for SimpleBackend<S>
+impl<S, T> Backend<S, T> for SimpleBackend<S, T>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
 {
     #[cfg_attr(feature = "inline-more", inline)]
     fn with_capacity(cap: usize) -> Self {
         Self {
             strings: Vec::with_capacity(cap),
+            string_bytes: 0,
             symbol_marker: Default::default(),
         }
     }
 
     #[inline]
-    fn intern(&mut self, string: &str) -> S {
+    fn intern(&mut self, string: &T) -> S {
         let symbol = expect_valid_symbol(self.strings.len());
-        let str = string.to_string().into_boxed_str();
+        let str = Box::<[u8]>::from(string.as_bytes());
+        self.string_bytes += str.len();
         self.strings.push(str);
         symbol
     }
 
     #[inline]
-    fn resolve(&self, symbol: S) -> Option<&str> {
-        self.strings.get(symbol.to_usize()).map(|pinned| &**pinned)
+    fn try_intern(&mut self, string: &T) -> Result<S, InternerError> {
+        let symbol = try_valid_symbol(self.strings.len())?;
+        let string = string.as_bytes();
+        self.strings.try_reserve(1)?;
+        let mut str = Vec::new();
+        str.try_reserve_exact(string.len())?;
+        str.extend_from_slice(string);
+        self.string_bytes += str.len();
+        self.strings.push(str.into_boxed_slice());
+        Ok(symbol)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clear(&mut self) {
+        self.strings.clear();
+        self.string_bytes = 0;
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn reserve(&mut self, additional: usize, _additional_bytes: usize) {
+        // Every string has its own allocation so there is nothing to reserve
+        // for the string contents up front.
+        self.strings.reserve(additional);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn shrink_to_fit(&mut self) {
+        self.strings.shrink_to_fit();
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn capacity(&self) -> usize {
+        self.strings.capacity()
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        BackendMemoryStats {
+            string_bytes: self.string_bytes,
+            slack_bytes: 0,
+            index_len: self.strings.len(),
+            index_capacity: self.strings.capacity(),
+            index_bytes: self.strings.capacity() * mem::size_of::<Box<[u8]>>(),
+        }
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        self.strings.get(symbol.to_usize()).map(|pinned| {
+            // SAFETY: This is safe because all interned bytes originate from
+            //         values of type `T`.
+            unsafe { T::from_bytes_unchecked(pinned) }
+        })
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        T::from_bytes_unchecked(self.strings.get_unchecked(symbol.to_usize()))
+    }
+
+    #[inline]
+    fn inline(string: &T) -> Option<S> {
+        inline_symbol(string)
     }
 
     #[inline]
-    unsafe fn resolve_unchecked(&self, symbol: S) -> &str {
-        self.strings.get_unchecked(symbol.to_usize())
+    fn resolve_inline(symbol: &S) -> Option<&T> {
+        resolve_inline_symbol(symbol)
     }
 }
 
-impl<S> Clone for SimpleBackend<S> {
+impl<S, T: ?Sized> Clone for SimpleBackend<S, T> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn clone(&self) -> Self {
         Self {
             strings: self.strings.clone(),
+            string_bytes: self.string_bytes,
             symbol_marker: Default::default(),
         }
     }
 }
 
-impl<S> Eq for SimpleBackend<S> where S: Symbol {}
+impl<S, T: ?Sized> Eq for SimpleBackend<S, T> where S: Symbol {}
 
-impl<S> PartialEq for SimpleBackend<S>
+impl<S, T: ?Sized> PartialEq for SimpleBackend<S, T>
 where
     S: Symbol,
 {
@@ -108,12 +187,44 @@ where
     }
 }
 
-impl<'a, S> IntoIterator for &'a SimpleBackend<S>
+impl<S, T> Resolver<S, T> for SimpleBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Backend::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(self, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Box::new(Iter::new(self))
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a SimpleBackend<S, T>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
 {
-    type Item = (S, &'a str);
-    type IntoIter = Iter<'a, S>;
+    type Item = (S, &'a T);
+    type IntoIter = Iter<'a, S, T>;
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_iter(self) -> Self::IntoIter {
@@ -121,14 +232,14 @@ where
     }
 }
 
-pub struct Iter<'a, S> {
-    iter: Enumerate<slice::Iter<'a, Box<str>>>,
-    symbol_marker: PhantomData<fn() -> S>,
+pub struct Iter<'a, S, T: ?Sized> {
+    iter: Enumerate<slice::Iter<'a, Box<[u8]>>>,
+    symbol_marker: PhantomData<fn() -> (S, &'a T)>,
 }
 
-impl<'a, S> Iter<'a, S> {
+impl<'a, S, T: ?Sized> Iter<'a, S, T> {
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn new(backend: &'a SimpleBackend<S>) -> Self {
+    pub fn new(backend: &'a SimpleBackend<S, T>) -> Self {
         Self {
             iter: backend.strings.iter().enumerate(),
             symbol_marker: Default::default(),
@@ -136,11 +247,12 @@ impl<'a, S> Iter<'a, S> {
     }
 }
 
-impl<'a, S> Iterator for Iter<'a, S>
+impl<'a, S, T> Iterator for Iter<'a, S, T>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
 {
-    type Item = (S, &'a str);
+    type Item = (S, &'a T);
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -149,8 +261,11 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter
-            .next()
-            .map(|(id, pinned)| (expect_valid_symbol(id), &**pinned))
+        self.iter.next().map(|(id, pinned)| {
+            // SAFETY: This is safe because all interned bytes originate from
+            //         values of type `T`.
+            let string = unsafe { T::from_bytes_unchecked(pinned) };
+            (expect_valid_symbol(id), string)
+        })
     }
 }
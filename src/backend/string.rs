@@ -1,23 +1,39 @@
 #![cfg(feature = "backends")]
 
-use super::Backend;
+use super::{
+    inline_symbol,
+    resolve_inline_symbol,
+    Backend,
+};
 use crate::{
     compat::{
-        String,
+        Box,
         Vec,
     },
-    symbol::expect_valid_symbol,
+    symbol::{
+        expect_valid_symbol,
+        try_valid_symbol,
+    },
+    BackendMemoryStats,
+    ByteStr,
+    InternerError,
+    Resolver,
     Symbol,
 };
 use core::{
-    convert::TryInto,
+    convert::TryFrom,
     iter::Enumerate,
     marker::PhantomData,
+    mem,
     slice,
 };
 
 /// An interner backend that appends all interned strings together.
 ///
+/// The offset type `O` bounds the total length of all interned strings, e.g.
+/// the default `u32` allows for up to 4 GiB of string data. Use `u64` or `usize`
+/// offsets for more string data at the cost of a larger index.
+///
 /// # Note
 ///
 /// Implementation inspired by [CAD97's](https://github.com/CAD97) research
@@ -35,31 +51,75 @@ use core::{
 /// | Scenario    |  Rating  |
 /// |:------------|:--------:|
 /// | Fill        | **good** |
-/// | Resolve     | **bad**   |
+/// | Resolve     | **ok**   |
 /// | Allocations | **good** |
 /// | Footprint   | **good**   |
 /// | Supports `get_or_intern_static` | **no** |
 /// | `Send` + `Sync` | **yes** |
 #[derive(Debug)]
-pub struct StringBackend<S> {
-    ends: Vec<u32>,
-    buffer: String,
-    marker: PhantomData<fn() -> S>,
+pub struct StringBackend<S, T: ?Sized = str, O = u32> {
+    /// The end offsets of all interned strings preceded by a leading `0`.
+    ///
+    /// The leading `0` is the start offset of the first string so that the
+    /// span of every string is made up of two adjacent entries. It is pushed
+    /// together with the first string so that empty backends do not allocate.
+    ends: Vec<O>,
+    buffer: Vec<u8>,
+    marker: PhantomData<fn() -> (S, *const T)>,
+}
+
+/// Offsets into the buffer of a [`StringBackend`].
+///
+/// Implemented for `u16`, `u32`, `u64` and `usize`.
+pub trait Offset: Copy + Eq {
+    /// Creates an offset from a `usize`.
+    ///
+    /// Returns `None` if `value` is out of bounds for the offset.
+    fn try_from_usize(value: usize) -> Option<Self>;
+
+    /// The offset of the start of the buffer.
+    const ZERO: Self;
+
+    /// Returns the `usize` representation of `self`.
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_offset {
+    ( $($ty:ty),* ) => {
+        $(
+            impl Offset for $ty {
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn try_from_usize(value: usize) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
 }
+impl_offset!(u16, u32, u64, usize);
 
 /// Represents a `[from, to)` index into the `StringBackend` buffer.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Span {
-    from: u32,
-    to: u32,
+    from: usize,
+    to: usize,
 }
 
-impl<S> PartialEq for StringBackend<S>
+impl<S, T, O> PartialEq for StringBackend<S, T, O>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
+    O: Offset,
 {
     fn eq(&self, other: &Self) -> bool {
-        if self.ends.len() != other.ends.len() {
+        if self.len_strings() != other.len_strings() {
             return false
         }
         for ((_, lhs), (_, rhs)) in self.into_iter().zip(other) {
@@ -71,9 +131,15 @@ where
     }
 }
 
-impl<S> Eq for StringBackend<S> where S: Symbol {}
+impl<S, T, O> Eq for StringBackend<S, T, O>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+    O: Offset,
+{
+}
 
-impl<S> Clone for StringBackend<S> {
+impl<S, T: ?Sized, O: Clone> Clone for StringBackend<S, T, O> {
     fn clone(&self) -> Self {
         Self {
             ends: self.ends.clone(),
@@ -83,115 +149,267 @@ impl<S> Clone for StringBackend<S> {
     }
 }
 
-impl<S> Default for StringBackend<S> {
+impl<S, T: ?Sized, O> Default for StringBackend<S, T, O> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn default() -> Self {
         Self {
             ends: Vec::default(),
-            buffer: String::default(),
+            buffer: Vec::default(),
             marker: Default::default(),
         }
     }
 }
 
-impl<S> StringBackend<S>
+impl<S, T, O> StringBackend<S, T, O>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
+    O: Offset,
 {
+    /// Returns the number of interned strings.
+    #[inline]
+    fn len_strings(&self) -> usize {
+        self.ends.len().saturating_sub(1)
+    }
+
     /// Returns the next available symbol.
     fn next_symbol(&self) -> S {
-        expect_valid_symbol(self.ends.len())
+        expect_valid_symbol(self.len_strings())
+    }
+
+    /// Pushes the leading `0` end offset if the backend has no strings, yet.
+    #[inline]
+    fn push_leading_end(&mut self) {
+        if self.ends.is_empty() {
+            self.ends.push(O::ZERO);
+        }
     }
 
     /// Returns the string associated to the span.
-    fn span_to_str(&self, span: Span) -> &str {
-        // SAFETY: - We convert a `String` into its underlying bytes and then
-        //           directly reinterpret it as `&str` again which is safe.
-        //         - Nothing mutates the string in between since this is a `&self`
+    fn span_to_str(&self, span: Span) -> &T {
+        // SAFETY: - The buffer only contains the bytes of values of type `T`
+        //           that we directly reinterpret as `&T` again which is safe.
+        //         - Nothing mutates the buffer in between since this is a `&self`
         //           method.
         //         - The spans we use for `(start..end]` ranges are always
-        //           constructed in accordance to valid utf8 byte ranges.
-        unsafe {
-            core::str::from_utf8_unchecked(
-                &self.buffer.as_bytes()[(span.from as usize)..(span.to as usize)],
-            )
-        }
+        //           constructed in accordance to the bytes of whole values.
+        unsafe { T::from_bytes_unchecked(&self.buffer[span.from..span.to]) }
     }
 
     /// Returns the span for the given symbol if any.
+    #[inline]
     fn symbol_to_span(&self, symbol: S) -> Option<Span> {
         let index = symbol.to_usize();
-        self.ends.get(index).copied().map(|to| {
-            let from = self.ends.get(index.wrapping_sub(1)).copied().unwrap_or(0);
-            Span { from, to }
-        })
+        match self.ends.get(index..index.checked_add(2)?)? {
+            [from, to] => {
+                Some(Span {
+                    from: from.to_usize(),
+                    to: to.to_usize(),
+                })
+            }
+            _ => None,
+        }
     }
 
-    /// Returns the span for the given symbol if any.
+    /// Returns the span for the given symbol.
+    ///
+    /// # Safety
+    ///
+    /// The symbol must have been handed out by this backend.
+    #[inline]
     unsafe fn symbol_to_span_unchecked(&self, symbol: S) -> Span {
         let index = symbol.to_usize();
-        let to = *self.ends.get_unchecked(index);
-        let from = self.ends.get(index.wrapping_sub(1)).copied().unwrap_or(0);
-        Span { from, to }
+        Span {
+            from: self.ends.get_unchecked(index).to_usize(),
+            to: self.ends.get_unchecked(index + 1).to_usize(),
+        }
     }
 
     /// Pushes the given string into the buffer and returns its span.
     ///
     /// # Panics
     ///
-    /// If the backend ran out of symbols.
-    fn push_string(&mut self, string: &str) -> S {
-        self.buffer.push_str(string);
+    /// - If the backend ran out of symbols.
+    /// - If the buffer grew beyond the bounds of the offset type.
+    fn push_string(&mut self, string: &T) -> S {
+        let string = string.as_bytes();
+        let to = O::try_from_usize(self.buffer.len() + string.len())
+            .expect("encountered buffer overflow, use a wider offset type");
+        let symbol = self.next_symbol();
+        self.buffer.extend_from_slice(string);
+        self.push_leading_end();
+        self.ends.push(to);
+        symbol
+    }
+
+    /// Pushes the given string into the buffer and returns its span.
+    ///
+    /// # Errors
+    ///
+    /// - If the backend ran out of symbols.
+    /// - If the buffer would grow beyond its maximum size.
+    /// - If memory allocation failed.
+    fn try_push_string(&mut self, string: &T) -> Result<S, InternerError> {
+        let symbol = try_valid_symbol(self.len_strings())?;
+        let string = string.as_bytes();
         let to = self
             .buffer
-            .as_bytes()
             .len()
-            .try_into()
-            .expect("ran out of symbols");
-        let symbol = self.next_symbol();
+            .checked_add(string.len())
+            .and_then(O::try_from_usize)
+            .ok_or(InternerError::StorageOverflow)?;
+        self.ends
+            .try_reserve(1 + usize::from(self.ends.is_empty()))?;
+        self.buffer.try_reserve(string.len())?;
+        self.buffer.extend_from_slice(string);
+        self.push_leading_end();
         self.ends.push(to);
-        symbol
+        Ok(symbol)
     }
 }
 
-impl<S> Backend<S> for StringBackend<S>
+impl<S, T, O> Backend<S, T> for StringBackend<S, T, O>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
+    O: Offset,
 {
     #[cfg_attr(feature = "inline-more", inline)]
     fn with_capacity(cap: usize) -> Self {
         // According to google the approx. word length is 5.
         let default_word_len = 5;
         Self {
-            ends: Vec::with_capacity(cap),
-            buffer: String::with_capacity(cap * default_word_len),
+            ends: Vec::with_capacity(cap + 1),
+            buffer: Vec::with_capacity(cap * default_word_len),
             marker: Default::default(),
         }
     }
 
     #[inline]
-    fn intern(&mut self, string: &str) -> S {
+    fn intern(&mut self, string: &T) -> S {
         self.push_string(string)
     }
 
     #[inline]
-    fn resolve(&self, symbol: S) -> Option<&str> {
+    fn try_intern(&mut self, string: &T) -> Result<S, InternerError> {
+        self.try_push_string(string)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clear(&mut self) {
+        self.ends.clear();
+        self.buffer.clear();
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn reserve(&mut self, additional: usize, additional_bytes: usize) {
+        self.ends
+            .reserve(additional + usize::from(self.ends.is_empty()));
+        self.buffer.reserve(additional_bytes);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn shrink_to_fit(&mut self) {
+        self.ends.shrink_to_fit();
+        self.buffer.shrink_to_fit();
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn capacity(&self) -> usize {
+        self.ends.capacity().saturating_sub(1)
+    }
+
+    fn extend_from(&mut self, other: &Self) -> Option<usize> {
+        let shift = self.len_strings();
+        let len = other.len_strings();
+        if len == 0 {
+            return Some(shift)
+        }
+        S::try_from_usize(shift.checked_add(len - 1)?)?;
+        let base = self.buffer.len();
+        O::try_from_usize(base.checked_add(other.buffer.len())?)?;
+        self.buffer.extend_from_slice(&other.buffer);
+        self.push_leading_end();
+        self.ends.extend(other.ends[1..].iter().map(|end| {
+            O::try_from_usize(base + end.to_usize())
+                .expect("the last end offset fits the offset type and so do all others")
+        }));
+        Some(shift)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        BackendMemoryStats {
+            string_bytes: self.buffer.len(),
+            slack_bytes: self.buffer.capacity() - self.buffer.len(),
+            index_len: self.len_strings(),
+            index_capacity: self.ends.capacity(),
+            index_bytes: self.ends.capacity() * mem::size_of::<O>(),
+        }
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
         self.symbol_to_span(symbol)
             .map(|span| self.span_to_str(span))
     }
 
     #[inline]
-    unsafe fn resolve_unchecked(&self, symbol: S) -> &str {
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
         self.span_to_str(self.symbol_to_span_unchecked(symbol))
     }
+
+    #[inline]
+    fn inline(string: &T) -> Option<S> {
+        inline_symbol(string)
+    }
+
+    #[inline]
+    fn resolve_inline(symbol: &S) -> Option<&T> {
+        resolve_inline_symbol(symbol)
+    }
+}
+
+impl<S, T, O> Resolver<S, T> for StringBackend<S, T, O>
+where
+    S: Symbol,
+    T: ?Sized + ByteStr,
+    O: Offset,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len_strings()
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Backend::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(self, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Box::new(Iter::new(self))
+    }
 }
 
-impl<'a, S> IntoIterator for &'a StringBackend<S>
+impl<'a, S, T, O> IntoIterator for &'a StringBackend<S, T, O>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
+    O: Offset,
 {
-    type Item = (S, &'a str);
-    type IntoIter = Iter<'a, S>;
+    type Item = (S, &'a T);
+    type IntoIter = Iter<'a, S, T, O>;
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_iter(self) -> Self::IntoIter {
@@ -199,28 +417,30 @@ where
     }
 }
 
-pub struct Iter<'a, S> {
-    backend: &'a StringBackend<S>,
-    start: u32,
-    ends: Enumerate<slice::Iter<'a, u32>>,
+pub struct Iter<'a, S, T: ?Sized, O = u32> {
+    backend: &'a StringBackend<S, T, O>,
+    start: usize,
+    ends: Enumerate<slice::Iter<'a, O>>,
 }
 
-impl<'a, S> Iter<'a, S> {
+impl<'a, S, T: ?Sized, O> Iter<'a, S, T, O> {
     #[cfg_attr(feature = "inline-more", inline)]
-    pub fn new(backend: &'a StringBackend<S>) -> Self {
+    pub fn new(backend: &'a StringBackend<S, T, O>) -> Self {
         Self {
             backend,
             start: 0,
-            ends: backend.ends.iter().enumerate(),
+            ends: backend.ends.get(1..).unwrap_or(&[]).iter().enumerate(),
         }
     }
 }
 
-impl<'a, S> Iterator for Iter<'a, S>
+impl<'a, S, T, O> Iterator for Iter<'a, S, T, O>
 where
     S: Symbol,
+    T: ?Sized + ByteStr,
+    O: Offset,
 {
-    type Item = (S, &'a str);
+    type Item = (S, &'a T);
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -229,7 +449,8 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.ends.next().map(|(id, &to)| {
+        self.ends.next().map(|(id, to)| {
+            let to = to.to_usize();
             let from = core::mem::replace(&mut self.start, to);
             (
                 expect_valid_symbol(id),
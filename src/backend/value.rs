@@ -0,0 +1,285 @@
+#![cfg(feature = "backends")]
+
+use super::Backend;
+use crate::{
+    compat::{
+        Box,
+        ToOwned,
+        Vec,
+    },
+    symbol::{
+        expect_valid_symbol,
+        try_valid_symbol,
+    },
+    BackendMemoryStats,
+    InternerError,
+    Resolver,
+    Symbol,
+};
+use core::{
+    borrow::Borrow,
+    fmt,
+    fmt::{
+        Debug,
+        Formatter,
+    },
+    iter::Enumerate,
+    marker::PhantomData,
+    mem,
+    slice,
+};
+
+/// A backend that stores an owned copy of every interned value.
+///
+/// Unlike the other backends this backend is not restricted to string types
+/// and can be used with any `T` that implements [`ToOwned`], e.g. tuples,
+/// `[u32]` slices or type signatures.
+///
+/// # Usage
+///
+/// - **Fill:** Efficiency of filling an empty string interner.
+/// - **Resolve:** Efficiency of interned string look-up given a symbol.
+/// - **Allocations:** The number of allocations performed by the backend.
+/// - **Footprint:** The total heap memory consumed by the backend.
+///
+/// Rating varies between **bad**, **ok** and **good**.
+///
+/// | Scenario    |  Rating  |
+/// |:------------|:--------:|
+/// | Fill        | **bad** |
+/// | Resolve     | **good**   |
+/// | Allocations | **bad** |
+/// | Footprint   | **bad**   |
+/// | Supports `get_or_intern_static` | **no** |
+/// | `Send` + `Sync` | **yes** |
+pub struct ValueBackend<S, T>
+where
+    T: ?Sized + ToOwned,
+{
+    values: Vec<T::Owned>,
+    symbol_marker: PhantomData<fn() -> S>,
+}
+
+impl<S, T> Debug for ValueBackend<S, T>
+where
+    T: ?Sized + ToOwned,
+    T::Owned: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueBackend")
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+impl<S, T> Default for ValueBackend<S, T>
+where
+    T: ?Sized + ToOwned,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T> Backend<S, T> for ValueBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ToOwned,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity(cap: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(cap),
+            symbol_marker: Default::default(),
+        }
+    }
+
+    #[inline]
+    fn intern(&mut self, value: &T) -> S {
+        let symbol = expect_valid_symbol(self.values.len());
+        self.values.push(value.to_owned());
+        symbol
+    }
+
+    #[inline]
+    fn try_intern(&mut self, value: &T) -> Result<S, InternerError> {
+        let symbol = try_valid_symbol(self.values.len())?;
+        self.values.try_reserve(1)?;
+        self.values.push(value.to_owned());
+        Ok(symbol)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn reserve(&mut self, additional: usize, _additional_bytes: usize) {
+        self.values.reserve(additional);
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn shrink_to_fit(&mut self) {
+        self.values.shrink_to_fit();
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Reports the values as string data.
+    ///
+    /// # Note
+    ///
+    /// Heap memory owned by the values themselves is not accounted for.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn memory_stats(&self) -> BackendMemoryStats {
+        let value_size = mem::size_of::<T::Owned>();
+        BackendMemoryStats {
+            string_bytes: self.values.len() * value_size,
+            slack_bytes: (self.values.capacity() - self.values.len()) * value_size,
+            index_len: self.values.len(),
+            index_capacity: self.values.capacity(),
+            index_bytes: 0,
+        }
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        self.values.get(symbol.to_usize()).map(Borrow::borrow)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        self.values.get_unchecked(symbol.to_usize()).borrow()
+    }
+}
+
+impl<S, T> Clone for ValueBackend<S, T>
+where
+    T: ?Sized + ToOwned,
+    T::Owned: Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<S, T> Eq for ValueBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ToOwned + Eq,
+{
+}
+
+impl<S, T> PartialEq for ValueBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ToOwned + PartialEq,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn eq(&self, other: &Self) -> bool {
+        self.values.len() == other.values.len()
+            && self
+                .values
+                .iter()
+                .zip(&other.values)
+                .all(|(lhs, rhs)| lhs.borrow() == rhs.borrow())
+    }
+}
+
+impl<S, T> Resolver<S, T> for ValueBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ToOwned,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Backend::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(self, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Box::new(Iter::new(self))
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a ValueBackend<S, T>
+where
+    S: Symbol,
+    T: ?Sized + ToOwned + 'a,
+{
+    type Item = (S, &'a T);
+    type IntoIter = Iter<'a, S, T>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter::new(self)
+    }
+}
+
+pub struct Iter<'a, S, T>
+where
+    T: ?Sized + ToOwned,
+{
+    iter: Enumerate<slice::Iter<'a, T::Owned>>,
+    symbol_marker: PhantomData<fn() -> S>,
+}
+
+impl<'a, S, T> Iter<'a, S, T>
+where
+    T: ?Sized + ToOwned,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(backend: &'a ValueBackend<S, T>) -> Self {
+        Self {
+            iter: backend.values.iter().enumerate(),
+            symbol_marker: Default::default(),
+        }
+    }
+}
+
+impl<'a, S, T> Iterator for Iter<'a, S, T>
+where
+    S: Symbol,
+    T: ?Sized + ToOwned + 'a,
+{
+    type Item = (S, &'a T);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(id, value)| (expect_valid_symbol(id), value.borrow()))
+    }
+}
@@ -0,0 +1,274 @@
+//! Interners whose symbols cannot be mixed up with those of other interners.
+//!
+//! Every [`BrandedInterner`] carries a unique, invariant lifetime `'id` as its
+//! brand. The [`BrandedSymbol`]s it hands out carry the same brand, so resolving
+//! a symbol with a different interner fails to compile.
+
+use crate::{
+    backend::Backend,
+    dedup::DedupKey,
+    DefaultBackend,
+    DefaultHashBuilder,
+    DefaultSymbol,
+    Interner,
+    Symbol,
+};
+use core::{
+    fmt,
+    fmt::{
+        Debug,
+        Formatter,
+    },
+    hash::{
+        BuildHasher,
+        Hash,
+        Hasher,
+    },
+    marker::PhantomData,
+};
+
+/// An invariant lifetime brand that is unique for every branded interner.
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+/// A symbol that can only be resolved by the [`BrandedInterner`] that created it.
+///
+/// Use [`BrandedSymbol::into_inner`] to obtain the plain symbol, e.g. for storage.
+pub struct BrandedSymbol<'id, S> {
+    symbol: S,
+    brand: Brand<'id>,
+}
+
+impl<'id, S> BrandedSymbol<'id, S>
+where
+    S: Symbol,
+{
+    /// Returns the plain symbol without its brand.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.symbol
+    }
+}
+
+impl<'id, S> Debug for BrandedSymbol<'id, S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BrandedSymbol").field(&self.symbol).finish()
+    }
+}
+
+impl<'id, S> Copy for BrandedSymbol<'id, S> where S: Copy {}
+
+impl<'id, S> Clone for BrandedSymbol<'id, S>
+where
+    S: Copy,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'id, S> PartialEq for BrandedSymbol<'id, S>
+where
+    S: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol == other.symbol
+    }
+}
+
+impl<'id, S> Eq for BrandedSymbol<'id, S> where S: Eq {}
+
+impl<'id, S> Hash for BrandedSymbol<'id, S>
+where
+    S: Hash,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.symbol.hash(state)
+    }
+}
+
+/// An interner branded with a unique lifetime `'id`.
+///
+/// Created via [`Interner::branded`]. Its symbols are [`BrandedSymbol`]s with the
+/// same brand so that resolving them can neither fail nor return a string of a
+/// different interner.
+///
+/// # Note
+///
+/// A branded interner provides no way to remove strings and cannot be cloned
+/// since both would allow symbols that no longer refer to their string.
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::StringInterner;
+/// StringInterner::default().branded(|mut interner| {
+///     let foo = interner.get_or_intern("foo");
+///     assert_eq!(interner.resolve(&foo), "foo");
+/// });
+/// ```
+///
+/// Symbols of one branded interner cannot be used with another one:
+///
+/// ```compile_fail
+/// # use string_interner::StringInterner;
+/// StringInterner::default().branded(|mut a| {
+///     StringInterner::default().branded(|b| {
+///         let foo = a.get_or_intern("foo");
+///         b.resolve(&foo);
+///     });
+/// });
+/// ```
+pub struct BrandedInterner<
+    'id,
+    T: ?Sized = str,
+    S = DefaultSymbol,
+    B = DefaultBackend<S, T>,
+    H = DefaultHashBuilder,
+    K = S,
+> where
+    S: Symbol,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    interner: Interner<T, S, B, H, K>,
+    brand: Brand<'id>,
+}
+
+impl<'id, T, S, B, H, K> Debug for BrandedInterner<'id, T, S, B, H, K>
+where
+    T: ?Sized,
+    S: Symbol + Debug,
+    B: Backend<S, T> + Debug,
+    H: BuildHasher,
+    K: DedupKey<S> + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BrandedInterner")
+            .field(&self.interner)
+            .finish()
+    }
+}
+
+impl<T, S, B, H, K> Interner<T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Brands the interner with a unique lifetime for the duration of `f`.
+    ///
+    /// Use [`BrandedInterner::into_inner`] within `f` to get the interner back.
+    #[inline]
+    pub fn branded<F, R>(self, f: F) -> R
+    where
+        F: for<'id> FnOnce(BrandedInterner<'id, T, S, B, H, K>) -> R,
+    {
+        f(BrandedInterner {
+            interner: self,
+            brand: PhantomData,
+        })
+    }
+}
+
+impl<'id, T, S, B, H, K> BrandedInterner<'id, T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Brands the given symbol.
+    #[inline]
+    fn brand(symbol: S) -> BrandedSymbol<'id, S> {
+        BrandedSymbol {
+            symbol,
+            brand: PhantomData,
+        }
+    }
+
+    /// Returns the number of strings interned by the interner.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interner.len()
+    }
+
+    /// Returns `true` if the string interner has no interned strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interner.is_empty()
+    }
+
+    /// Returns the symbol for the given string if any.
+    #[inline]
+    pub fn get<U>(&self, string: U) -> Option<BrandedSymbol<'id, S>>
+    where
+        U: AsRef<T>,
+    {
+        self.interner.get(string).map(Self::brand)
+    }
+
+    /// Interns the given string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern<U>(&mut self, string: U) -> BrandedSymbol<'id, S>
+    where
+        U: AsRef<T>,
+    {
+        Self::brand(self.interner.get_or_intern(string))
+    }
+
+    /// Interns the given `'static` string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern_static(&mut self, string: &'static T) -> BrandedSymbol<'id, S> {
+        Self::brand(self.interner.get_or_intern_static(string))
+    }
+
+    /// Returns the string for the given symbol.
+    ///
+    /// Unlike [`Interner::resolve`] this cannot fail since the brand guarantees
+    /// that the symbol has been created by this interner.
+    ///
+    /// The symbol is borrowed so that symbols which encode their string inline
+    /// can be resolved, too.
+    #[inline]
+    pub fn resolve<'a>(&'a self, symbol: &'a BrandedSymbol<'id, S>) -> &'a T {
+        if let Some(string) = B::resolve_inline(&symbol.symbol) {
+            return string
+        }
+        // SAFETY: The brand guarantees that the symbol has been handed out by this
+        //         interner which never removes strings and cannot be cloned.
+        //         Symbols that encode their string inline have been resolved above
+        //         and are the only symbols not stored by the backend.
+        unsafe { self.interner.resolve_unchecked(symbol.symbol) }
+    }
+
+    /// Returns the underlying interner.
+    ///
+    /// Its symbols are the plain symbols returned by [`BrandedSymbol::into_inner`].
+    #[inline]
+    pub fn into_inner(self) -> Interner<T, S, B, H, K> {
+        self.interner
+    }
+}
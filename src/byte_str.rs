@@ -0,0 +1,105 @@
+//! String types that can be interned by the byte based backends.
+
+use core::hash::Hash;
+
+/// String types that the backends store as contiguous bytes.
+///
+/// This allows the same backends to intern [`str`], raw `[u8]` byte strings
+/// and, with the `std` crate feature, `OsStr` and `Path`.
+///
+/// # Safety
+///
+/// Implementors must guarantee that [`ByteStr::from_bytes_unchecked`] returns
+/// a value equal to `self` when given the bytes returned by [`ByteStr::as_bytes`].
+/// It must also accept any valid UTF-8 since strings encoded inline by a
+/// [`Symbol`](`crate::Symbol`) are resolved from their UTF-8 bytes.
+pub unsafe trait ByteStr: Hash + Eq {
+    /// Returns the underlying bytes of `self`.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Reinterprets the given bytes as `Self`.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must have been returned by [`ByteStr::as_bytes`] of a value
+    /// of the same type or be valid UTF-8.
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self;
+
+    /// Reinterprets the given bytes as `Self` if they are valid for `Self`.
+    ///
+    /// # Note
+    ///
+    /// The default implementation accepts exactly the valid UTF-8.
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Option<&Self> {
+        let string = core::str::from_utf8(bytes).ok()?;
+        // SAFETY: `ByteStr` types accept any valid UTF-8.
+        Some(unsafe { Self::from_bytes_unchecked(string.as_bytes()) })
+    }
+}
+
+unsafe impl ByteStr for str {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    #[inline]
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        core::str::from_utf8_unchecked(bytes)
+    }
+}
+
+unsafe impl ByteStr for [u8] {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        bytes
+    }
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Option<&Self> {
+        Some(bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl ByteStr for std::ffi::OsStr {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.as_encoded_bytes()
+    }
+
+    #[inline]
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        std::ffi::OsStr::from_encoded_bytes_unchecked(bytes)
+    }
+
+    #[cfg(unix)]
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Option<&Self> {
+        Some(std::os::unix::ffi::OsStrExt::from_bytes(bytes))
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl ByteStr for std::path::Path {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.as_os_str().as_encoded_bytes()
+    }
+
+    #[inline]
+    unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
+        std::path::Path::new(std::ffi::OsStr::from_encoded_bytes_unchecked(bytes))
+    }
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Option<&Self> {
+        <std::ffi::OsStr as ByteStr>::from_bytes(bytes).map(std::path::Path::new)
+    }
+}
@@ -13,18 +13,28 @@ pub use ::hashbrown::{
 cfg_if! {
     if #[cfg(feature = "std")] {
         pub use ::std::{
+            collections::TryReserveError,
             vec,
             vec::Vec,
-            string::{String, ToString},
             boxed::Box,
         };
+        #[cfg(feature = "backends")]
+        pub use ::std::{
+            borrow::ToOwned,
+            sync::Arc,
+        };
     } else {
         extern crate alloc;
         pub use self::alloc::{
+            collections::TryReserveError,
             vec,
             vec::Vec,
-            string::{String, ToString},
             boxed::Box,
         };
+        #[cfg(feature = "backends")]
+        pub use self::alloc::{
+            borrow::ToOwned,
+            sync::Arc,
+        };
     }
 }
@@ -0,0 +1,130 @@
+//! Layouts of the deduplication table of an [`Interner`](`crate::Interner`).
+//!
+//! The deduplication table stores a key for every interned string. By default
+//! the key is the bare symbol of the string. Growing the table then resolves
+//! and re-hashes every interned string and every probe compares strings.
+//!
+//! An interner with [`CachedHash`] keys instead stores the hash of every string
+//! next to its symbol. Growing the table then never reads the interned strings
+//! and probes only compare strings whose hashes are equal. This costs 8 bytes
+//! per table entry and pays off for longer strings or slower hashers, whereas
+//! re-hashing short strings is about as cheap as reading the cached hash.
+//!
+//! # Example
+//!
+//! ```
+//! # use string_interner::{
+//! #     backend::StringBackend,
+//! #     dedup::CachedHash,
+//! #     DefaultHashBuilder,
+//! #     DefaultSymbol,
+//! #     StringInterner,
+//! # };
+//! type Interner = StringInterner<
+//!     DefaultSymbol,
+//!     StringBackend<DefaultSymbol>,
+//!     DefaultHashBuilder,
+//!     CachedHash<DefaultSymbol>,
+//! >;
+//!
+//! let mut interner = Interner::new();
+//! let foo = interner.get_or_intern("foo");
+//! assert_eq!(interner.get("foo"), Some(foo));
+//! assert_eq!(interner.resolve(foo), Some("foo"));
+//! ```
+
+use crate::Symbol;
+
+mod private {
+    /// Prevents implementations of [`DedupKey`](`super::DedupKey`) outside of
+    /// this crate since the interners trust the symbols of the keys.
+    pub trait Sealed<S> {}
+}
+
+/// A key of the deduplication table of an interner with symbols of type `S`.
+///
+/// Implemented by all [`Symbol`] types themselves and by [`CachedHash`].
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait DedupKey<S>: Copy + private::Sealed<S> {
+    /// Creates a new key for the symbol whose string has the given hash.
+    #[doc(hidden)]
+    fn new(symbol: S, hash: u64) -> Self;
+
+    /// Returns the symbol of the key.
+    #[doc(hidden)]
+    fn symbol(self) -> S;
+
+    /// Returns the hash of the string of the key.
+    ///
+    /// Computes the hash via `hash_fn` unless it is cached.
+    #[doc(hidden)]
+    fn hash_or_else(self, hash_fn: impl FnOnce(S) -> u64) -> u64;
+
+    /// Returns `false` if the string of the key certainly differs from a string
+    /// with the given hash.
+    #[doc(hidden)]
+    fn may_match(self, hash: u64) -> bool;
+}
+
+impl<S> private::Sealed<S> for S where S: Symbol {}
+
+impl<S> DedupKey<S> for S
+where
+    S: Symbol,
+{
+    #[inline]
+    fn new(symbol: S, _hash: u64) -> Self {
+        symbol
+    }
+
+    #[inline]
+    fn symbol(self) -> S {
+        self
+    }
+
+    #[inline]
+    fn hash_or_else(self, hash_fn: impl FnOnce(S) -> u64) -> u64 {
+        hash_fn(self)
+    }
+
+    #[inline]
+    fn may_match(self, _hash: u64) -> bool {
+        true
+    }
+}
+
+/// A deduplication table key that caches the hash of its string.
+///
+/// See the [module documentation](`self`) for details.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CachedHash<S> {
+    symbol: S,
+    hash: u64,
+}
+
+impl<S> private::Sealed<S> for CachedHash<S> where S: Symbol {}
+
+impl<S> DedupKey<S> for CachedHash<S>
+where
+    S: Symbol,
+{
+    #[inline]
+    fn new(symbol: S, hash: u64) -> Self {
+        Self { symbol, hash }
+    }
+
+    #[inline]
+    fn symbol(self) -> S {
+        self.symbol
+    }
+
+    #[inline]
+    fn hash_or_else(self, _hash_fn: impl FnOnce(S) -> u64) -> u64 {
+        self.hash
+    }
+
+    #[inline]
+    fn may_match(self, hash: u64) -> bool {
+        self.hash == hash
+    }
+}
@@ -0,0 +1,197 @@
+//! Entries of an interner for a single string.
+//!
+//! Created via [`Interner::entry`](`crate::Interner::entry`).
+
+use crate::{
+    backend::Backend,
+    compat::hash_map::RawVacantEntryMut,
+    dedup::DedupKey,
+    interner::key_hash,
+    Symbol,
+};
+use core::{
+    fmt,
+    fmt::{
+        Debug,
+        Formatter,
+    },
+    hash::{
+        BuildHasher,
+        Hash,
+    },
+    marker::PhantomData,
+};
+
+/// A view into a single string of an interner which is either interned or not.
+///
+/// Created via [`Interner::entry`](`crate::Interner::entry`).
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{Entry, StringInterner};
+/// let mut interner = StringInterner::default();
+/// let foo = match interner.entry("foo") {
+///     Entry::Occupied(_) => unreachable!(),
+///     Entry::Vacant(vacant) => vacant.insert_static(),
+/// };
+/// assert!(matches!(interner.entry("foo"), Entry::Occupied(symbol) if symbol == foo));
+/// ```
+pub enum Entry<'a, 's, T: ?Sized, S, B, H, K = S> {
+    /// The string has already been interned and resolves to the symbol.
+    Occupied(S),
+    /// The string has not been interned, yet.
+    Vacant(VacantEntry<'a, 's, T, S, B, H, K>),
+}
+
+impl<'a, 's, T, S, B, H, K> Entry<'a, 's, T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Returns the symbol of the string, interning it if necessary.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn or_insert(self) -> S {
+        match self {
+            Self::Occupied(symbol) => symbol,
+            Self::Vacant(vacant) => vacant.insert(),
+        }
+    }
+}
+
+impl<'a, 's, T, S, B, H, K> Debug for Entry<'a, 's, T, S, B, H, K>
+where
+    T: ?Sized + Debug,
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Occupied(symbol) => f.debug_tuple("Occupied").field(symbol).finish(),
+            Self::Vacant(vacant) => f.debug_tuple("Vacant").field(vacant).finish(),
+        }
+    }
+}
+
+/// A view into a string that has not been interned, yet.
+///
+/// Hashing and probing have already been done so that inserting the string
+/// does not repeat them.
+pub struct VacantEntry<'a, 's, T: ?Sized, S, B, H, K = S> {
+    entry: RawVacantEntryMut<'a, K, (), ()>,
+    backend: &'a mut B,
+    hasher: &'a H,
+    string: &'s T,
+    hash: u64,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<'a, 's, T, S, B, H, K> VacantEntry<'a, 's, T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Creates a new vacant entry for the string with the given hash.
+    #[inline]
+    pub(crate) fn new(
+        entry: RawVacantEntryMut<'a, K, (), ()>,
+        backend: &'a mut B,
+        hasher: &'a H,
+        string: &'s T,
+        hash: u64,
+    ) -> Self {
+        Self {
+            entry,
+            backend,
+            hasher,
+            string,
+            hash,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the string of the entry.
+    #[inline]
+    pub fn string(&self) -> &'s T {
+        self.string
+    }
+
+    /// Interns the string of the entry using the given intern function.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn insert_using(self, intern_fn: fn(&mut B, &'s T) -> S) -> S {
+        let Self {
+            entry,
+            backend,
+            hasher,
+            string,
+            hash,
+            ..
+        } = self;
+        let symbol = intern_fn(backend, string);
+        entry.insert_with_hasher(hash, K::new(symbol, hash), (), |&key| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            unsafe { key_hash(backend, hasher, key) }
+        });
+        symbol
+    }
+
+    /// Interns the string of the entry.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn insert(self) -> S {
+        self.insert_using(B::intern)
+    }
+}
+
+impl<'a, T, S, B, H, K> VacantEntry<'a, 'static, T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Interns the `'static` string of the entry.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Note
+    ///
+    /// This is more efficient than [`VacantEntry::insert`] since it might
+    /// avoid some memory allocations if the backends supports this.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn insert_static(self) -> S {
+        self.insert_using(B::intern_static)
+    }
+}
+
+impl<'a, 's, T, S, B, H, K> Debug for VacantEntry<'a, 's, T, S, B, H, K>
+where
+    T: ?Sized + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VacantEntry").field(&self.string).finish()
+    }
+}
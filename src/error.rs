@@ -0,0 +1,38 @@
+//! Errors that may occur when interning strings.
+
+use core::fmt;
+
+/// Errors that may occur when interning a string.
+///
+/// Returned by the fallible [`StringInterner::try_get_or_intern`](`crate::StringInterner::try_get_or_intern`)
+/// and [`Backend::try_intern`](`crate::backend::Backend::try_intern`) methods.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InternerError {
+    /// The symbol type cannot represent any more interned strings.
+    SymbolsExhausted,
+    /// The backend cannot store any more string data.
+    StorageOverflow,
+    /// Allocating memory for the interned string failed.
+    AllocationFailed,
+}
+
+impl fmt::Display for InternerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::SymbolsExhausted => "ran out of symbols",
+            Self::StorageOverflow => "ran out of backend storage",
+            Self::AllocationFailed => "failed to allocate memory",
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InternerError {}
+
+impl From<crate::compat::TryReserveError> for InternerError {
+    #[inline]
+    fn from(_: crate::compat::TryReserveError) -> Self {
+        Self::AllocationFailed
+    }
+}
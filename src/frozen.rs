@@ -0,0 +1,357 @@
+//! Immutable interners that can be shared between threads without locking.
+//!
+//! Freeze an interner via [`Interner::freeze`] once it is no longer written to.
+
+use crate::{
+    backend::Backend,
+    compat::Box,
+    dedup::DedupKey,
+    DefaultBackend,
+    DefaultHashBuilder,
+    DefaultSymbol,
+    Interner,
+    Resolver,
+    Symbol,
+};
+use core::{
+    fmt,
+    fmt::{
+        Debug,
+        Formatter,
+    },
+    hash::{
+        BuildHasher,
+        Hash,
+    },
+    marker::PhantomData,
+};
+
+/// An interner that can no longer intern new strings.
+///
+/// Since it provides no way to mutate it, a frozen interner is `Send` and `Sync`
+/// whenever its backend, symbol and hash builder are and thus can be shared
+/// between threads via an `Arc` without any locking.
+///
+/// Created via [`Interner::freeze`].
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::StringInterner;
+/// use std::{sync::Arc, thread};
+///
+/// let mut interner = StringInterner::default();
+/// let foo = interner.get_or_intern("foo");
+/// let frozen = Arc::new(interner.freeze());
+/// let shared = Arc::clone(&frozen);
+/// thread::spawn(move || assert_eq!(shared.resolve(foo), Some("foo")))
+///     .join()
+///     .unwrap();
+/// assert_eq!(frozen.get("foo"), Some(foo));
+/// ```
+pub struct FrozenInterner<
+    T: ?Sized = str,
+    S = DefaultSymbol,
+    B = DefaultBackend<S, T>,
+    H = DefaultHashBuilder,
+    K = S,
+> where
+    S: Symbol,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    interner: Interner<T, S, B, H, K>,
+}
+
+impl<T, S, B, H, K> Debug for FrozenInterner<T, S, B, H, K>
+where
+    T: ?Sized,
+    S: Symbol + Debug,
+    B: Backend<S, T> + Debug,
+    H: BuildHasher,
+    K: DedupKey<S> + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FrozenInterner")
+            .field(&self.interner)
+            .finish()
+    }
+}
+
+impl<T, S, B, H, K> Clone for FrozenInterner<T, S, B, H, K>
+where
+    T: ?Sized,
+    S: Symbol,
+    B: Backend<S, T> + Clone,
+    H: BuildHasher + Clone,
+    K: DedupKey<S>,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            interner: self.interner.clone(),
+        }
+    }
+}
+
+impl<T, S, B, H, K> Interner<T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Freezes the interner so that it can be shared between threads without locking.
+    ///
+    /// Releases unused capacity since a frozen interner never grows.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn freeze(mut self) -> FrozenInterner<T, S, B, H, K> {
+        self.shrink_to_fit();
+        FrozenInterner { interner: self }
+    }
+}
+
+impl<T, S, B, H, K> FrozenInterner<T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Returns the number of strings interned by the interner.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interner.len()
+    }
+
+    /// Returns `true` if the string interner has no interned strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interner.is_empty()
+    }
+
+    /// Returns the symbol for the given string if any.
+    #[inline]
+    pub fn get<U>(&self, string: U) -> Option<S>
+    where
+        U: AsRef<T>,
+    {
+        self.interner.get(string)
+    }
+
+    /// Returns the symbol for the given value if any.
+    ///
+    /// Unlike [`FrozenInterner::get`] this takes the value by reference and thus
+    /// does not require an [`AsRef`] implementation that most non-string types lack.
+    #[inline]
+    pub fn get_ref(&self, string: &T) -> Option<S> {
+        self.interner.get_ref(string)
+    }
+
+    /// Returns the string for the given symbol if any.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&T> {
+        self.interner.resolve(symbol)
+    }
+
+    /// Returns the string for the given symbol if any.
+    ///
+    /// Also resolves symbols that encode their string inline, see
+    /// [`Interner::resolve_ref`].
+    #[inline]
+    pub fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        self.interner.resolve_ref(symbol)
+    }
+
+    /// Returns the mutable interner so that new strings can be interned again.
+    #[inline]
+    pub fn unfreeze(self) -> Interner<T, S, B, H, K> {
+        self.interner
+    }
+
+    /// Drops the deduplication table so that strings can only be resolved.
+    ///
+    /// This saves the memory of the deduplication table if strings are never
+    /// looked up by their contents anymore.
+    #[inline]
+    pub fn into_resolver(self) -> FrozenResolver<T, S, B> {
+        FrozenResolver {
+            len: self.interner.len(),
+            backend: self.interner.into_backend(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, S, B, H, K> IntoIterator for &'a FrozenInterner<T, S, B, H, K>
+where
+    T: ?Sized + 'a,
+    S: Symbol,
+    B: Backend<S, T>,
+    &'a B: IntoIterator<Item = (S, &'a T)>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    type Item = (S, &'a T);
+    type IntoIter = <&'a B as IntoIterator>::IntoIter;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.interner.into_iter()
+    }
+}
+
+impl<T, S, B, H, K> Resolver<S, T> for FrozenInterner<T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T> + Resolver<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        Resolver::len(&self.interner)
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Resolver::resolve(&self.interner, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        self.interner.resolve_ref(symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Resolver::resolve_unchecked(&self.interner, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Resolver::iter(&self.interner)
+    }
+}
+
+/// A frozen interner without a deduplication table that can only resolve symbols.
+///
+/// Created via [`FrozenInterner::into_resolver`].
+pub struct FrozenResolver<T: ?Sized = str, S = DefaultSymbol, B = DefaultBackend<S, T>> {
+    /// The number of strings interned by the backend.
+    len: usize,
+    backend: B,
+    marker: PhantomData<fn(&T) -> S>,
+}
+
+impl<T, S, B> Debug for FrozenResolver<T, S, B>
+where
+    T: ?Sized,
+    B: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FrozenResolver")
+            .field(&self.backend)
+            .finish()
+    }
+}
+
+impl<T, S, B> Clone for FrozenResolver<T, S, B>
+where
+    T: ?Sized,
+    B: Clone,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn clone(&self) -> Self {
+        Self {
+            len: self.len,
+            backend: self.backend.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, S, B> FrozenResolver<T, S, B>
+where
+    T: ?Sized,
+    S: Symbol,
+    B: Backend<S, T>,
+{
+    /// Returns the number of strings interned by the resolver.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the resolver has no interned strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the string for the given symbol if any.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&T> {
+        self.backend.resolve(symbol)
+    }
+
+    /// Returns the string for the given symbol if any.
+    ///
+    /// Also resolves symbols that encode their string inline, see
+    /// [`Interner::resolve_ref`].
+    #[inline]
+    pub fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        self.backend.resolve_ref(symbol)
+    }
+}
+
+impl<'a, T, S, B> IntoIterator for &'a FrozenResolver<T, S, B>
+where
+    T: ?Sized + 'a,
+    S: Symbol,
+    B: Backend<S, T>,
+    &'a B: IntoIterator<Item = (S, &'a T)>,
+{
+    type Item = (S, &'a T);
+    type IntoIter = <&'a B as IntoIterator>::IntoIter;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.backend.into_iter()
+    }
+}
+
+impl<T, S, B> Resolver<S, T> for FrozenResolver<T, S, B>
+where
+    T: ?Sized,
+    S: Symbol,
+    B: Backend<S, T> + Resolver<S, T>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(&self.backend, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        FrozenResolver::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(&self.backend, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Resolver::iter(&self.backend)
+    }
+}
@@ -0,0 +1,186 @@
+//! A process-global string interner.
+//!
+//! Strings interned into the global interner live for the entire lifetime of the
+//! program. The resulting [`GlobalSymbol`]s can be resolved from anywhere
+//! without passing an interner around.
+//!
+//! # Example
+//!
+//! ```
+//! use string_interner::{global, intern};
+//!
+//! let foo = global::intern("foo");
+//! assert_eq!(foo, intern!("foo"));
+//! assert_eq!(foo.as_str(), "foo");
+//! assert_eq!(foo.to_string(), "foo");
+//! assert_eq!(global::get("foo"), Some(foo));
+//! ```
+
+use crate::{
+    backend::LeakingBackend,
+    symbol::SymbolU32,
+    DefaultHashBuilder,
+    Interner,
+    Symbol,
+};
+use core::fmt;
+use std::sync::{
+    OnceLock,
+    PoisonError,
+    RwLock,
+};
+
+/// The interner backing the global interner functions.
+type GlobalInterner =
+    Interner<str, GlobalSymbol, LeakingBackend<GlobalSymbol>, DefaultHashBuilder>;
+
+/// Returns the lazily initialized global interner.
+fn global() -> &'static RwLock<GlobalInterner> {
+    static GLOBAL: OnceLock<RwLock<GlobalInterner>> = OnceLock::new();
+    GLOBAL.get_or_init(|| RwLock::new(GlobalInterner::new()))
+}
+
+/// Interns the given string into the global interner.
+///
+/// # Panics
+///
+/// If the global interner ran out of symbols.
+#[inline]
+pub fn intern(string: &str) -> GlobalSymbol {
+    intern_using(string, GlobalInterner::get_or_intern)
+}
+
+/// Interns the given `'static` string into the global interner.
+///
+/// This is more efficient than [`intern`] since the string is not copied.
+///
+/// # Panics
+///
+/// If the global interner ran out of symbols.
+#[inline]
+pub fn intern_static(string: &'static str) -> GlobalSymbol {
+    intern_using(string, GlobalInterner::get_or_intern_static)
+}
+
+/// Interns the given string using the given intern function.
+///
+/// Only takes the write lock if the string has not been interned before.
+fn intern_using<'a>(
+    string: &'a str,
+    intern_fn: fn(&mut GlobalInterner, &'a str) -> GlobalSymbol,
+) -> GlobalSymbol {
+    if let Some(symbol) = get(string) {
+        return symbol
+    }
+    let mut interner = global().write().unwrap_or_else(PoisonError::into_inner);
+    intern_fn(&mut interner, string)
+}
+
+/// Returns the global symbol for the given string if it has been interned.
+#[inline]
+pub fn get(string: &str) -> Option<GlobalSymbol> {
+    global()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(string)
+}
+
+/// Returns the string for the given global symbol if any.
+#[inline]
+pub fn resolve(symbol: GlobalSymbol) -> Option<&'static str> {
+    global()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .backend()
+        .resolve_static(symbol)
+}
+
+/// A symbol of the global interner.
+///
+/// Created via [`intern`], [`intern_static`] or the [`intern!`](`crate::intern!`)
+/// macro. Formatting a global symbol prints its string, or its index if the
+/// symbol has not been interned by the global interner.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlobalSymbol(SymbolU32);
+
+impl GlobalSymbol {
+    /// Returns the string of the global symbol.
+    ///
+    /// # Panics
+    ///
+    /// If the symbol was not created by the global interner but manually
+    /// via [`Symbol::try_from_usize`] for an index that has not been interned.
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        resolve(self).expect("encountered invalid global symbol")
+    }
+}
+
+impl Symbol for GlobalSymbol {
+    #[inline]
+    fn try_from_usize(index: usize) -> Option<Self> {
+        SymbolU32::try_from_usize(index).map(Self)
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize {
+        self.0.to_usize()
+    }
+}
+
+impl fmt::Debug for GlobalSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match resolve(*self) {
+            Some(string) => fmt::Debug::fmt(string, f),
+            None => {
+                f.debug_tuple("GlobalSymbol")
+                    .field(&self.to_usize())
+                    .finish()
+            }
+        }
+    }
+}
+
+impl fmt::Display for GlobalSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match resolve(*self) {
+            Some(string) => f.write_str(string),
+            None => fmt::Display::fmt(&self.to_usize(), f),
+        }
+    }
+}
+
+impl AsRef<str> for GlobalSymbol {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Interns the given string literal into the global interner.
+///
+/// The symbol is cached per call site so that only the first evaluation
+/// accesses the global interner.
+///
+/// # Example
+///
+/// ```
+/// use string_interner::intern;
+///
+/// let symbols = (0..3).map(|_| intern!("foo")).collect::<Vec<_>>();
+/// assert_eq!(symbols[0], symbols[2]);
+/// assert_eq!(symbols[0].as_str(), "foo");
+/// ```
+#[macro_export]
+macro_rules! intern {
+    ($string:literal) => {{
+        static SYMBOL: $crate::global::__private::OnceLock<$crate::global::GlobalSymbol> =
+            $crate::global::__private::OnceLock::new();
+        *SYMBOL.get_or_init(|| $crate::global::intern_static($string))
+    }};
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use std::sync::OnceLock;
+}
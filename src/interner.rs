@@ -1,14 +1,28 @@
 use crate::{
-    backend::Backend,
+    backend::{
+        Backend,
+        RemovableBackend,
+    },
     compat::{
+        Box,
         DefaultHashBuilder,
         HashMap,
+        Vec,
     },
+    dedup::DedupKey,
+    entry::VacantEntry,
+    stats::hash_table_bytes,
     DefaultBackend,
     DefaultSymbol,
+    Entry,
+    InternerError,
+    MemoryStats,
+    Resolver,
     Symbol,
+    SymbolRemap,
 };
 use core::{
+    convert::Infallible,
     fmt,
     fmt::{
         Debug,
@@ -20,10 +34,12 @@ use core::{
         Hasher,
     },
     iter::FromIterator,
+    marker::PhantomData,
+    mem,
 };
 
 /// Creates the `u64` hash value for the given value using the given hash builder.
-fn make_hash<T>(builder: &impl BuildHasher, value: &T) -> u64
+pub(crate) fn make_hash<T>(builder: &impl BuildHasher, value: &T) -> u64
 where
     T: ?Sized + Hash,
 {
@@ -32,6 +48,32 @@ where
     state.finish()
 }
 
+/// Returns the hash of the string of the given deduplication table key.
+///
+/// The string is only resolved and hashed if the key does not cache its hash.
+///
+/// # Safety
+///
+/// The symbol of the key must have been handed out by the backend and must not
+/// encode its string inline.
+#[inline]
+pub(crate) unsafe fn key_hash<T, S, B, K>(
+    backend: &B,
+    builder: &impl BuildHasher,
+    key: K,
+) -> u64
+where
+    T: ?Sized + Hash,
+    S: Symbol,
+    B: Backend<S, T>,
+    K: DedupKey<S>,
+{
+    key.hash_or_else(|symbol| make_hash(builder, backend.resolve_unchecked(symbol)))
+}
+
+/// The number of strings that bulk interning hashes in a single batch.
+const INTERN_MANY_CHUNK: usize = 32;
+
 /// Data structure to intern and resolve strings.
 ///
 /// Caches strings efficiently, with minimal memory footprint and associates them with unique symbols.
@@ -43,27 +85,73 @@ where
 ///     - This maps from `string` type to `symbol` type.
 /// - [`StringInterner::resolve`]: To resolve your already interned strings.
 ///     - This maps from `symbol` type to `string` type.
-pub struct StringInterner<
+pub type StringInterner<
     S = DefaultSymbol,
     B = DefaultBackend<S>,
     H = DefaultHashBuilder,
+    K = S,
+> = Interner<str, S, B, H, K>;
+
+/// Data structure to intern and resolve byte strings.
+///
+/// Works exactly like the [`StringInterner`] but interns `[u8]` byte strings
+/// that are not required to be valid UTF-8.
+pub type BytesInterner<
+    S = DefaultSymbol,
+    B = DefaultBackend<S, [u8]>,
+    H = DefaultHashBuilder,
+    K = S,
+> = Interner<[u8], S, B, H, K>;
+
+/// Data structure to intern and resolve values of type `T`.
+///
+/// This is the generic data structure behind the [`StringInterner`] and the
+/// [`BytesInterner`] and can intern any `T` that implements [`Hash`] and [`Eq`].
+/// Most backends provided by this crate can intern all [`ByteStr`](`crate::ByteStr`)
+/// types, e.g. `OsStr` and `Path` with the `std` crate feature enabled, whereas
+/// the [`ValueBackend`](`crate::backend::ValueBackend`) supports arbitrary values.
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{Interner, DefaultSymbol, backend::ValueBackend};
+/// let mut interner = <Interner<(u32, u32), DefaultSymbol, ValueBackend<_, _>>>::new();
+/// let sym0 = interner.get_or_intern_ref(&(1, 2));
+/// let sym1 = interner.get_or_intern_ref(&(3, 4));
+/// assert_ne!(sym0, sym1);
+/// assert_eq!(interner.get_or_intern_ref(&(1, 2)), sym0);
+/// assert_eq!(interner.resolve(sym1), Some(&(3, 4)));
+/// ```
+///
+/// The key type `K` selects the layout of the deduplication table, see the
+/// [`dedup`](`crate::dedup`) module.
+pub struct Interner<
+    T: ?Sized = str,
+    S = DefaultSymbol,
+    B = DefaultBackend<S, T>,
+    H = DefaultHashBuilder,
+    K = S,
 > where
     S: Symbol,
     H: BuildHasher,
+    K: DedupKey<S>,
 {
-    dedup: HashMap<S, (), ()>,
+    dedup: HashMap<K, (), ()>,
     hasher: H,
     backend: B,
+    marker: PhantomData<fn(&T) -> S>,
 }
 
-impl<S, B, H> Debug for StringInterner<S, B, H>
+impl<T, S, B, H, K> Debug for Interner<T, S, B, H, K>
 where
+    T: ?Sized,
     S: Symbol + Debug,
-    B: Backend<S> + Debug,
+    B: Backend<S, T> + Debug,
     H: BuildHasher,
+    K: DedupKey<S> + Debug,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("StringInterner")
+        f.debug_struct("Interner")
             .field("dedup", &self.dedup)
             .field("backend", &self.backend)
             .finish()
@@ -71,97 +159,143 @@ where
 }
 
 #[cfg(feature = "backends")]
-impl Default for StringInterner {
+impl<T> Default for Interner<T>
+where
+    T: ?Sized + Hash + Eq,
+    DefaultBackend<DefaultSymbol, T>: Backend<DefaultSymbol, T>,
+{
     #[cfg_attr(feature = "inline-more", inline)]
     fn default() -> Self {
-        StringInterner::new()
+        Interner::new()
     }
 }
 
-impl<S, B, H> Clone for StringInterner<S, B, H>
+impl<T, S, B, H, K> Clone for Interner<T, S, B, H, K>
 where
+    T: ?Sized,
     S: Symbol,
-    B: Backend<S> + Clone,
+    B: Backend<S, T> + Clone,
     H: BuildHasher + Clone,
+    K: DedupKey<S>,
 {
     fn clone(&self) -> Self {
         Self {
             dedup: self.dedup.clone(),
             hasher: self.hasher.clone(),
             backend: self.backend.clone(),
+            marker: Default::default(),
         }
     }
 }
 
-impl<S, B, H> PartialEq for StringInterner<S, B, H>
+impl<T, S, B, H, K> PartialEq for Interner<T, S, B, H, K>
 where
+    T: ?Sized,
     S: Symbol,
-    B: Backend<S> + PartialEq,
+    B: Backend<S, T> + PartialEq,
     H: BuildHasher,
+    K: DedupKey<S>,
 {
     fn eq(&self, rhs: &Self) -> bool {
-        self.len() == rhs.len() && self.backend == rhs.backend
+        self.dedup.len() == rhs.dedup.len() && self.backend == rhs.backend
     }
 }
 
-impl<S, B, H> Eq for StringInterner<S, B, H>
+impl<T, S, B, H, K> Eq for Interner<T, S, B, H, K>
 where
+    T: ?Sized,
     S: Symbol,
-    B: Backend<S> + Eq,
+    B: Backend<S, T> + Eq,
     H: BuildHasher,
+    K: DedupKey<S>,
 {
 }
 
-impl<S, B, H> StringInterner<S, B, H>
+impl<T, S, B, H, K> Interner<T, S, B, H, K>
 where
+    T: ?Sized + Hash + Eq,
     S: Symbol,
-    B: Backend<S>,
+    B: Backend<S, T>,
     H: BuildHasher + Default,
+    K: DedupKey<S>,
 {
-    /// Creates a new empty `StringInterner`.
+    /// Creates a new empty `Interner`.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn new() -> Self {
         Self {
             dedup: HashMap::default(),
             hasher: Default::default(),
             backend: B::default(),
+            marker: Default::default(),
         }
     }
 
-    /// Creates a new `StringInterner` with the given initial capacity.
+    /// Creates a new `Interner` with the given initial capacity.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn with_capacity(cap: usize) -> Self {
         Self {
             dedup: HashMap::with_capacity_and_hasher(cap, ()),
             hasher: Default::default(),
             backend: B::with_capacity(cap),
+            marker: Default::default(),
+        }
+    }
+
+    /// Creates a new `Interner` that has interned all strings of the given table.
+    ///
+    /// The `i`-th string of the table is associated to the symbol with index `i`.
+    /// This is meant to be used with tables generated by the
+    /// [`symbols!`](`crate::symbols!`) macro.
+    ///
+    /// Strings are interned via [`Interner::get_or_intern_static`] so backends
+    /// supporting it do not copy them.
+    ///
+    /// # Panics
+    ///
+    /// If the table contains duplicates or if the backend does not hand out
+    /// dense symbols since then not every string would resolve to its index.
+    #[inline]
+    pub fn with_prefilled(table: &[&'static T]) -> Self {
+        let mut interner = Self::with_capacity(table.len());
+        for (index, &string) in table.iter().enumerate() {
+            let symbol = interner.get_or_intern_static(string);
+            assert_eq!(
+                symbol.to_usize(),
+                index,
+                "encountered duplicate or misplaced string in prefilled table"
+            );
         }
+        interner
     }
 }
 
-impl<S, B, H> StringInterner<S, B, H>
+impl<T, S, B, H, K> Interner<T, S, B, H, K>
 where
+    T: ?Sized + Hash + Eq,
     S: Symbol,
-    B: Backend<S>,
+    B: Backend<S, T>,
     H: BuildHasher,
+    K: DedupKey<S>,
 {
-    /// Creates a new empty `StringInterner` with the given hasher.
+    /// Creates a new empty `Interner` with the given hasher.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn with_hasher(hash_builder: H) -> Self {
-        StringInterner {
+        Interner {
             dedup: HashMap::default(),
             hasher: hash_builder,
             backend: B::default(),
+            marker: Default::default(),
         }
     }
 
-    /// Creates a new empty `StringInterner` with the given initial capacity and the given hasher.
+    /// Creates a new empty `Interner` with the given initial capacity and the given hasher.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn with_capacity_and_hasher(cap: usize, hash_builder: H) -> Self {
-        StringInterner {
+        Interner {
             dedup: HashMap::with_capacity_and_hasher(cap, ()),
             hasher: hash_builder,
             backend: B::with_capacity(cap),
+            marker: Default::default(),
         }
     }
 
@@ -177,67 +311,493 @@ where
         self.len() == 0
     }
 
-    /// Returns the symbol for the given string if any.
+    /// Returns a shared reference to the backend of the interner.
+    #[cfg(any(feature = "global", feature = "backends"))]
+    #[inline]
+    pub(crate) fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Returns the backend of the interner, dropping the deduplication table.
+    #[inline]
+    pub(crate) fn into_backend(self) -> B {
+        self.backend
+    }
+
+    /// Returns statistics about the heap memory used by the interner.
     ///
-    /// Can be used to query if a string has already been interned without interning.
+    /// This is cheap to compute and does not iterate over the interned strings.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn memory_stats(&self) -> MemoryStats {
+        MemoryStats {
+            backend: self.backend.memory_stats(),
+            dedup_len: self.dedup.len(),
+            dedup_capacity: self.dedup.capacity(),
+            dedup_bytes: hash_table_bytes::<K>(self.dedup.capacity()),
+        }
+    }
+
+    /// Returns the total number of heap bytes allocated by the interner.
+    ///
+    /// Shorthand for `self.memory_stats().heap_size()`.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn heap_size(&self) -> usize {
+        self.memory_stats().heap_size()
+    }
+
+    /// Returns the number of strings the interner can hold without reallocating.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn capacity(&self) -> usize {
+        usize::min(self.dedup.capacity(), self.backend.capacity())
+    }
+
+    /// Removes all interned strings while keeping the allocated memory for reuse.
+    ///
+    /// Symbols handed out before clearing must no longer be used with the interner.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn clear(&mut self) {
+        self.dedup.clear();
+        self.backend.clear();
+    }
+
+    /// Reserves capacity for at least `additional` more strings that span
+    /// `additional_bytes` bytes in total.
     #[inline]
-    pub fn get<T>(&self, string: T) -> Option<S>
+    pub fn reserve(&mut self, additional: usize, additional_bytes: usize) {
+        let required = self
+            .len()
+            .checked_add(additional)
+            .expect("capacity overflow");
+        if self.dedup.capacity() < required {
+            self.rebuild_dedup(required);
+        }
+        self.backend.reserve(additional, additional_bytes);
+    }
+
+    /// Shrinks the capacity of the interner as much as possible.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        if self.dedup.capacity() > self.len() {
+            self.rebuild_dedup(self.len());
+        }
+        self.backend.shrink_to_fit();
+    }
+
+    /// Rebuilds the deduplication table with the given capacity.
+    ///
+    /// # Note
+    ///
+    /// The table has no hasher of its own and thus cannot be resized in place.
+    /// Keys that cache their hash are moved without resolving their strings.
+    fn rebuild_dedup(&mut self, capacity: usize) {
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            ..
+        } = self;
+        use crate::compat::hash_map::RawEntryMut;
+        let mut rebuilt = HashMap::with_capacity_and_hasher(capacity, ());
+        for &key in dedup.keys() {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            let hash = unsafe { key_hash(backend, hasher, key) };
+            // All symbols are distinct so there never is an occupied entry.
+            if let RawEntryMut::Vacant(vacant) =
+                rebuilt.raw_entry_mut().from_hash(hash, |_| false)
+            {
+                vacant.insert_with_hasher(hash, key, (), |&key| {
+                    // SAFETY: This is safe because we only operate on symbols that
+                    //         we receive from our backend making them valid.
+                    unsafe { key_hash(backend, hasher, key) }
+                });
+            }
+        }
+        *dedup = rebuilt;
+    }
+
+    /// Interns all strings of `other` and returns the mapping of their symbols.
+    ///
+    /// The returned [`SymbolRemap`] maps every symbol of `other` to the symbol
+    /// of the same string in `self`.
+    ///
+    /// Strings that symbols of `other` encode inline are not stored by `other`
+    /// and thus not interned into `self`. If the symbol type of `self` cannot
+    /// encode them inline as well, [`SymbolRemap::get`] returns `None` for their
+    /// symbols. Use [`SymbolRemap::apply_or_intern`] to map them.
+    ///
+    /// # Note
+    ///
+    /// Reserves space for all strings of `other` up front. Merging into an empty
+    /// interner also skips all string comparisons since the strings of `other`
+    /// are known to be distinct. Apart from that every string of `other` is
+    /// interned one by one. Use [`Interner::merge_from_same_backend`] to copy
+    /// the strings in bulk if both interners use the same symbol and backend types.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    pub fn merge_from<S2, B2, H2, K2>(
+        &mut self,
+        other: &Interner<T, S2, B2, H2, K2>,
+    ) -> SymbolRemap<S2, S>
     where
-        T: AsRef<str>,
+        S2: Symbol,
+        B2: Backend<S2, T> + Resolver<S2, T>,
+        H2: BuildHasher,
+        K2: DedupKey<S2>,
     {
-        let string = string.as_ref();
+        self.reserve(other.len(), other.memory_stats().backend.string_bytes);
+        let mut remap = SymbolRemap::with_capacity(other.len());
+        let strings = Resolver::iter(&other.backend);
+        if self.is_empty() {
+            for (from, string) in strings {
+                let hash = make_hash(&self.hasher, string);
+                remap.insert(from, self.intern_distinct(string, hash));
+            }
+        } else {
+            for (from, string) in strings {
+                remap.insert(from, self.get_or_intern_using(string, B::intern));
+            }
+        }
+        remap
+    }
+
+    /// Interns all strings of `other` which uses the same symbol and backend types.
+    ///
+    /// Works like [`Interner::merge_from`] but appends the strings of `other`
+    /// to an empty interner in bulk via [`Backend::extend_from`] if the backend
+    /// supports it, such as [`StringBackend`](`crate::backend::StringBackend`)
+    /// and [`BufferBackend`](`crate::backend::BufferBackend`). Only the
+    /// deduplication table is then rebuilt string by string.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    pub fn merge_from_same_backend<H2, K2>(
+        &mut self,
+        other: &Interner<T, S, B, H2, K2>,
+    ) -> SymbolRemap<S>
+    where
+        B: Resolver<S, T>,
+        H2: BuildHasher,
+        K2: DedupKey<S>,
+    {
+        if !self.is_empty() {
+            return self.merge_from(other)
+        }
+        let shift = match self.backend.extend_from(&other.backend) {
+            Some(shift) => shift,
+            None => return self.merge_from(other),
+        };
         let Self {
             dedup,
             hasher,
             backend,
+            ..
         } = self;
-        let hash = make_hash(hasher, string);
+        use crate::compat::hash_map::RawEntryMut;
+        if dedup.capacity() < other.len() {
+            *dedup = HashMap::with_capacity_and_hasher(other.len(), ());
+        }
+        let mut remap = SymbolRemap::with_capacity(other.len());
+        for from in other.dedup.keys().map(|key| key.symbol()) {
+            let to = S::try_from_usize(from.to_usize() + shift)
+                .expect("the backend checked that all shifted symbols are valid");
+            // SAFETY: The backend guarantees that the shifted symbols of `other`
+            //         are valid symbols of `self`.
+            let string = unsafe { Backend::resolve_unchecked(backend, to) };
+            let hash = make_hash(hasher, string);
+            // The strings of `other` are distinct so there never is an occupied entry.
+            if let RawEntryMut::Vacant(vacant) =
+                dedup.raw_entry_mut().from_hash(hash, |_| false)
+            {
+                vacant.insert_with_hasher(hash, K::new(to, hash), (), |&key| {
+                    // SAFETY: This is safe because we only operate on symbols that
+                    //         we receive from our backend making them valid.
+                    unsafe { key_hash(backend, hasher, key) }
+                });
+            }
+            remap.insert(from, to);
+        }
+        remap
+    }
+
+    /// Interns the given string with the given `hash` without deduplication.
+    ///
+    /// The caller must ensure that the string has not been interned before.
+    fn intern_distinct(&mut self, string: &T, hash: u64) -> S {
+        if let Some(symbol) = B::inline(string) {
+            return symbol
+        }
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            ..
+        } = self;
+        let symbol = backend.intern(string);
+        use crate::compat::hash_map::RawEntryMut;
+        if let RawEntryMut::Vacant(vacant) =
+            dedup.raw_entry_mut().from_hash(hash, |_| false)
+        {
+            vacant.insert_with_hasher(hash, K::new(symbol, hash), (), |&key| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe { key_hash(backend, hasher, key) }
+            });
+        }
+        symbol
+    }
+
+    /// Returns the symbol for the given string if any.
+    ///
+    /// Can be used to query if a string has already been interned without interning.
+    #[inline]
+    pub fn get<U>(&self, string: U) -> Option<S>
+    where
+        U: AsRef<T>,
+    {
+        self.get_ref(string.as_ref())
+    }
+
+    /// Returns the symbol for the given value if any.
+    ///
+    /// Unlike [`Interner::get`] this takes the value by reference and thus does not
+    /// require an [`AsRef`] implementation that most non-string types lack.
+    #[inline]
+    pub fn get_ref(&self, string: &T) -> Option<S> {
+        self.get_hashed(string, |hasher| make_hash(hasher, string))
+    }
+
+    /// Returns the hash of the given string as computed by the interner.
+    ///
+    /// This is the hash expected by [`Interner::get_with_hash`] and
+    /// [`Interner::get_or_intern_with_hash`].
+    #[inline]
+    pub fn hash_of(&self, string: &T) -> u64 {
+        make_hash(&self.hasher, string)
+    }
+
+    /// Returns the hash builder of the interner.
+    #[inline]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Asserts in debug builds that `hash` is the interner's hash of `string`.
+    #[inline]
+    fn debug_assert_hash(&self, hash: u64, string: &T) {
+        debug_assert_eq!(
+            hash,
+            make_hash(&self.hasher, string),
+            "encountered hash that is inconsistent with the interner's hash builder"
+        );
+    }
+
+    /// Returns the symbol for the given string with the precomputed `hash` if any.
+    ///
+    /// # Note
+    ///
+    /// The `hash` must be equal to the hash of `string` as computed by the
+    /// interner's [`BuildHasher`], see [`Interner::hash_of`]. Otherwise the
+    /// string might not be found even if it has been interned.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds if `hash` is inconsistent with the interner's hash builder.
+    #[inline]
+    pub fn get_with_hash<U>(&self, hash: u64, string: U) -> Option<S>
+    where
+        U: AsRef<T>,
+    {
+        let string = string.as_ref();
+        self.debug_assert_hash(hash, string);
+        self.get_hashed(string, |_| hash)
+    }
+
+    /// Returns the symbol for the given string if any.
+    ///
+    /// The `hash` of the string is only computed if the string is not encoded
+    /// inline by the symbol.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_hashed(&self, string: &T, hash: impl FnOnce(&H) -> u64) -> Option<S> {
+        if let Some(symbol) = B::inline(string) {
+            return Some(symbol)
+        }
+        let hash = hash(&self.hasher);
+        let Self { dedup, backend, .. } = self;
         dedup
             .raw_entry()
-            .from_hash(hash, |symbol| {
+            .from_hash(hash, |key| {
                 // SAFETY: This is safe because we only operate on symbols that
                 //         we receive from our backend making them valid.
-                string == unsafe { backend.resolve_unchecked(*symbol) }
+                key.may_match(hash)
+                    && string == unsafe { backend.resolve_unchecked(key.symbol()) }
             })
-            .map(|(&symbol, &())| symbol)
+            .map(|(key, &())| key.symbol())
     }
 
     /// Interns the given string.
     ///
-    /// This is used as backend by [`get_or_intern`][1] and [`get_or_intern_static`][2].
+    /// This is used as backend by [`get_or_intern`][1], [`get_or_intern_static`][2]
+    /// and their fallible counterparts.
     ///
-    /// [1]: [`StringInterner::get_or_intern`]
-    /// [2]: [`StringInterner::get_or_intern_static`]
+    /// [1]: [`Interner::get_or_intern`]
+    /// [2]: [`Interner::get_or_intern_static`]
     #[cfg_attr(feature = "inline-more", inline)]
-    fn get_or_intern_using<T>(&mut self, string: T, intern_fn: fn(&mut B, T) -> S) -> S
-    where
-        T: Copy + Hash + AsRef<str> + for<'a> PartialEq<&'a str>,
-    {
+    pub(crate) fn try_get_or_intern_using<'a, E>(
+        &mut self,
+        string: &'a T,
+        intern_fn: impl FnOnce(&mut B, &'a T) -> Result<S, E>,
+    ) -> Result<S, E> {
+        self.try_get_or_intern_hashed_using(
+            string,
+            |hasher| make_hash(hasher, string),
+            intern_fn,
+        )
+    }
+
+    /// Interns the given string whose `hash` is computed by the given closure.
+    ///
+    /// The `hash` must be computed with the hash builder of the interner. It is
+    /// only computed if the string is not encoded inline by the symbol.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn try_get_or_intern_hashed_using<'a, E>(
+        &mut self,
+        string: &'a T,
+        hash: impl FnOnce(&H) -> u64,
+        intern_fn: impl FnOnce(&mut B, &'a T) -> Result<S, E>,
+    ) -> Result<S, E> {
+        // Strings that are encoded inline are never stored so that every string
+        // has a single symbol.
+        if let Some(symbol) = B::inline(string) {
+            return Ok(symbol)
+        }
+        let hash = hash(&self.hasher);
         let Self {
             dedup,
             hasher,
             backend,
+            ..
         } = self;
-        let hash = make_hash(hasher, string.as_ref());
-        let entry = dedup.raw_entry_mut().from_hash(hash, |symbol| {
+        let entry = dedup.raw_entry_mut().from_hash(hash, |key| {
             // SAFETY: This is safe because we only operate on symbols that
             //         we receive from our backend making them valid.
-            string == unsafe { backend.resolve_unchecked(*symbol) }
+            key.may_match(hash)
+                && string == unsafe { backend.resolve_unchecked(key.symbol()) }
         });
         use crate::compat::hash_map::RawEntryMut;
-        let (&mut symbol, &mut ()) = match entry {
+        let (&mut key, &mut ()) = match entry {
             RawEntryMut::Occupied(occupied) => occupied.into_key_value(),
             RawEntryMut::Vacant(vacant) => {
-                let symbol = intern_fn(backend, string);
-                vacant.insert_with_hasher(hash, symbol, (), |symbol| {
+                let symbol = intern_fn(backend, string)?;
+                vacant.insert_with_hasher(hash, K::new(symbol, hash), (), |&key| {
                     // SAFETY: This is safe because we only operate on symbols that
                     //         we receive from our backend making them valid.
-                    let string = unsafe { backend.resolve_unchecked(*symbol) };
-                    make_hash(hasher, string)
+                    unsafe { key_hash(backend, hasher, key) }
                 })
             }
         };
-        symbol
+        Ok(key.symbol())
+    }
+
+    /// Interns the given string.
+    ///
+    /// This is the infallible version of [`Interner::try_get_or_intern_using`].
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_or_intern_using<'a>(
+        &mut self,
+        string: &'a T,
+        intern_fn: fn(&mut B, &'a T) -> S,
+    ) -> S {
+        self.get_or_intern_hashed_using(
+            string,
+            |hasher| make_hash(hasher, string),
+            intern_fn,
+        )
+    }
+
+    /// Interns the given string whose `hash` is computed by the given closure.
+    ///
+    /// This is the infallible version of [`Interner::try_get_or_intern_hashed_using`].
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn get_or_intern_hashed_using<'a>(
+        &mut self,
+        string: &'a T,
+        hash: impl FnOnce(&H) -> u64,
+        intern_fn: fn(&mut B, &'a T) -> S,
+    ) -> S {
+        let result =
+            self.try_get_or_intern_hashed_using(string, hash, |backend, string| {
+                Ok::<_, Infallible>(intern_fn(backend, string))
+            });
+        match result {
+            Ok(symbol) => symbol,
+            Err(infallible) => match infallible {},
+        }
+    }
+
+    /// Interns all given strings and feeds their symbols in input order to `f`.
+    ///
+    /// Reserves space in the deduplication table for the lower bound of the
+    /// iterator's size hint up front and then streams the strings in chunks of
+    /// [`INTERN_MANY_CHUNK`]. The backend reserves the total byte length of every
+    /// chunk and its strings are hashed in a single batch before any of them is
+    /// interned.
+    fn intern_many_with<I, F>(&mut self, strings: I, mut f: F)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<T>,
+        F: FnMut(S),
+    {
+        let mut strings = strings.into_iter();
+        self.reserve(strings.size_hint().0, 0);
+        let mut chunk = Vec::with_capacity(INTERN_MANY_CHUNK);
+        let mut hashes = [0_u64; INTERN_MANY_CHUNK];
+        loop {
+            chunk.extend(strings.by_ref().take(INTERN_MANY_CHUNK));
+            if chunk.is_empty() {
+                break
+            }
+            let mut chunk_bytes = 0;
+            for (string, hash) in chunk.iter().zip(hashes.iter_mut()) {
+                chunk_bytes += mem::size_of_val(string.as_ref());
+                *hash = make_hash(&self.hasher, string.as_ref());
+            }
+            self.backend.reserve(chunk.len(), chunk_bytes);
+            for (string, &hash) in chunk.drain(..).zip(hashes.iter()) {
+                f(self.get_or_intern_hashed_using(string.as_ref(), |_| hash, B::intern));
+            }
+        }
+    }
+
+    /// Interns all given strings at once.
+    ///
+    /// Returns the symbols of the strings in input order.
+    ///
+    /// # Note
+    ///
+    /// This is more efficient than calling [`Interner::get_or_intern`] for every
+    /// string since the deduplication table is sized from the iterator's size
+    /// hint up front while the strings are hashed and their bytes reserved in
+    /// batches.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern_many<I>(&mut self, strings: I) -> Vec<S>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<T>,
+    {
+        let mut symbols = Vec::new();
+        self.intern_many_with(strings, |symbol| symbols.push(symbol));
+        symbols
     }
 
     /// Interns the given string.
@@ -249,20 +809,124 @@ where
     /// If the interner already interns the maximum number of strings possible
     /// by the chosen symbol type.
     #[inline]
-    pub fn get_or_intern<T>(&mut self, string: T) -> S
+    pub fn get_or_intern<U>(&mut self, string: U) -> S
     where
-        T: AsRef<str>,
+        U: AsRef<T>,
     {
         self.get_or_intern_using(string.as_ref(), B::intern)
     }
 
+    /// Interns the given string with the precomputed `hash`.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Note
+    ///
+    /// The `hash` must be equal to the hash of `string` as computed by the
+    /// interner's [`BuildHasher`], see [`Interner::hash_of`]. Otherwise the
+    /// string might be interned twice under different symbols.
+    ///
+    /// # Panics
+    ///
+    /// - In debug builds if `hash` is inconsistent with the interner's hash builder.
+    /// - If the interner already interns the maximum number of strings possible
+    ///   by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern_with_hash<U>(&mut self, hash: u64, string: U) -> S
+    where
+        U: AsRef<T>,
+    {
+        let string = string.as_ref();
+        self.debug_assert_hash(hash, string);
+        self.get_or_intern_hashed_using(string, |_| hash, B::intern)
+    }
+
+    /// Returns the entry of the given string.
+    ///
+    /// The entry is either occupied by the symbol of the already interned
+    /// string or vacant so that the string can be interned without hashing
+    /// it again.
+    ///
+    /// # Note
+    ///
+    /// Strings that are encoded inline by the symbol type are never stored and
+    /// have no notion of being interned for the first time. Their entry is thus
+    /// always occupied by their inline symbol.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn entry<'s>(&mut self, string: &'s T) -> Entry<'_, 's, T, S, B, H, K> {
+        if let Some(symbol) = B::inline(string) {
+            return Entry::Occupied(symbol)
+        }
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            ..
+        } = self;
+        let hash = make_hash(hasher, string);
+        let entry = dedup.raw_entry_mut().from_hash(hash, |key| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            key.may_match(hash)
+                && string == unsafe { backend.resolve_unchecked(key.symbol()) }
+        });
+        use crate::compat::hash_map::RawEntryMut;
+        match entry {
+            RawEntryMut::Occupied(occupied) => Entry::Occupied(occupied.key().symbol()),
+            RawEntryMut::Vacant(vacant) => {
+                Entry::Vacant(VacantEntry::new(vacant, backend, hasher, string, hash))
+            }
+        }
+    }
+
+    /// Interns the given string and reports whether it has been newly interned.
+    ///
+    /// Returns a symbol for resolution into the original string together with
+    /// `true` if the string has not been interned before.
+    ///
+    /// # Note
+    ///
+    /// Always returns `false` for strings that are encoded inline by the symbol
+    /// type, see [`Interner::entry`].
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern_inserted<U>(&mut self, string: U) -> (S, bool)
+    where
+        U: AsRef<T>,
+    {
+        match self.entry(string.as_ref()) {
+            Entry::Occupied(symbol) => (symbol, false),
+            Entry::Vacant(vacant) => (vacant.insert(), true),
+        }
+    }
+
+    /// Interns the given value.
+    ///
+    /// Returns a symbol for resolution into the original value.
+    ///
+    /// Unlike [`Interner::get_or_intern`] this takes the value by reference and thus
+    /// does not require an [`AsRef`] implementation that most non-string types lack.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of values possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern_ref(&mut self, value: &T) -> S {
+        self.get_or_intern_using(value, B::intern)
+    }
+
     /// Interns the given `'static` string.
     ///
     /// Returns a symbol for resolution into the original string.
     ///
     /// # Note
     ///
-    /// This is more efficient than [`StringInterner::get_or_intern`] since it might
+    /// This is more efficient than [`Interner::get_or_intern`] since it might
     /// avoid some memory allocations if the backends supports this.
     ///
     /// # Panics
@@ -270,61 +934,186 @@ where
     /// If the interner already interns the maximum number of strings possible
     /// by the chosen symbol type.
     #[inline]
-    pub fn get_or_intern_static(&mut self, string: &'static str) -> S {
+    pub fn get_or_intern_static(&mut self, string: &'static T) -> S {
         self.get_or_intern_using(string, B::intern_static)
     }
 
+    /// Interns the given string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Errors
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type or if the backend ran out of storage or memory.
+    ///
+    /// # Note
+    ///
+    /// Growing the internal deduplication table is still infallible and might
+    /// abort the process on memory exhaustion.
+    #[inline]
+    pub fn try_get_or_intern<U>(&mut self, string: U) -> Result<S, InternerError>
+    where
+        U: AsRef<T>,
+    {
+        self.try_get_or_intern_using(string.as_ref(), B::try_intern)
+    }
+
+    /// Interns the given `'static` string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Note
+    ///
+    /// This is more efficient than [`Interner::try_get_or_intern`] since it might
+    /// avoid some memory allocations if the backends supports this.
+    ///
+    /// # Errors
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type or if the backend ran out of storage or memory.
+    #[inline]
+    pub fn try_get_or_intern_static(
+        &mut self,
+        string: &'static T,
+    ) -> Result<S, InternerError> {
+        self.try_get_or_intern_using(string, B::try_intern_static)
+    }
+
     /// Returns the string for the given symbol if any.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` for symbols that encode their string inline since the
+    /// interner does not store it. Use [`Interner::resolve_ref`] instead.
     #[inline]
-    pub fn resolve(&self, symbol: S) -> Option<&str> {
+    pub fn resolve(&self, symbol: S) -> Option<&T> {
         self.backend.resolve(symbol)
     }
+
+    /// Returns the string for the given symbol if any.
+    ///
+    /// Unlike [`Interner::resolve`] this also resolves symbols that encode their
+    /// string inline, such as [`InlineSymbolU64`](`crate::symbol::InlineSymbolU64`),
+    /// by borrowing the string from the symbol.
+    #[inline]
+    pub fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        self.backend.resolve_ref(symbol)
+    }
+
+    /// Returns the string for the given symbol without checking its validity.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the symbol has been handed out by this interner,
+    /// that it does not encode its string inline and that its string has not been
+    /// removed since.
+    #[inline]
+    pub(crate) unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        self.backend.resolve_unchecked(symbol)
+    }
 }
 
-impl<S, B, H, T> FromIterator<T> for StringInterner<S, B, H>
+impl<T, S, B, H, K> Interner<T, S, B, H, K>
 where
+    T: ?Sized + Hash + Eq,
     S: Symbol,
-    B: Backend<S>,
+    B: RemovableBackend<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Removes the string for the given symbol from the interner.
+    ///
+    /// Returns `true` if the symbol referred to an interned string.
+    ///
+    /// Afterwards the symbol no longer resolves and interning the same string
+    /// again yields a new symbol.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::FreeListBackend, DefaultSymbol, StringInterner};
+    /// let mut interner = StringInterner::<DefaultSymbol, FreeListBackend<_>>::new();
+    /// let foo = interner.get_or_intern("foo");
+    /// assert!(interner.remove(foo));
+    /// assert_eq!(interner.resolve(foo), None);
+    /// assert_eq!(interner.get("foo"), None);
+    /// assert!(!interner.remove(foo));
+    ///
+    /// let bar = interner.get_or_intern("bar");
+    /// assert_ne!(foo, bar);
+    /// assert_eq!(interner.resolve(foo), None);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, symbol: S) -> bool {
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            ..
+        } = self;
+        let string = match backend.resolve(symbol) {
+            Some(string) => string,
+            None => return false,
+        };
+        let hash = make_hash(hasher, string);
+        use crate::compat::hash_map::RawEntryMut;
+        if let RawEntryMut::Occupied(occupied) = dedup
+            .raw_entry_mut()
+            .from_hash(hash, |key| key.symbol() == symbol)
+        {
+            occupied.remove();
+        }
+        backend.remove(symbol)
+    }
+}
+
+impl<T, S, B, H, K, U> FromIterator<U> for Interner<T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T>,
     H: BuildHasher + Default,
-    T: AsRef<str>,
+    U: AsRef<T>,
+    K: DedupKey<S>,
 {
     fn from_iter<I>(iter: I) -> Self
     where
-        I: IntoIterator<Item = T>,
+        I: IntoIterator<Item = U>,
     {
-        let iter = iter.into_iter();
-        let (capacity, _) = iter.size_hint();
-        let mut interner = Self::with_capacity(capacity);
+        let mut interner = Self::new();
         interner.extend(iter);
         interner
     }
 }
 
-impl<S, B, H, T> Extend<T> for StringInterner<S, B, H>
+impl<T, S, B, H, K, U> Extend<U> for Interner<T, S, B, H, K>
 where
+    T: ?Sized + Hash + Eq,
     S: Symbol,
-    B: Backend<S>,
+    B: Backend<S, T>,
     H: BuildHasher,
-    T: AsRef<str>,
+    U: AsRef<T>,
+    K: DedupKey<S>,
 {
     fn extend<I>(&mut self, iter: I)
     where
-        I: IntoIterator<Item = T>,
+        I: IntoIterator<Item = U>,
     {
-        for s in iter {
-            self.get_or_intern(s.as_ref());
-        }
+        self.intern_many_with(iter, |_| ());
     }
 }
 
-impl<'a, S, B, H> IntoIterator for &'a StringInterner<S, B, H>
+impl<'a, T, S, B, H, K> IntoIterator for &'a Interner<T, S, B, H, K>
 where
+    T: ?Sized + 'a,
     S: Symbol,
-    B: Backend<S>,
-    &'a B: IntoIterator<Item = (S, &'a str)>,
+    B: Backend<S, T>,
+    &'a B: IntoIterator<Item = (S, &'a T)>,
     H: BuildHasher,
+    K: DedupKey<S>,
 {
-    type Item = (S, &'a str);
+    type Item = (S, &'a T);
     type IntoIter = <&'a B as IntoIterator>::IntoIter;
 
     #[cfg_attr(feature = "inline-more", inline)]
@@ -332,3 +1121,511 @@ where
         self.backend.into_iter()
     }
 }
+
+impl<T, S, B, H, K> Resolver<S, T> for Interner<T, S, B, H, K>
+where
+    T: ?Sized + Hash + Eq,
+    S: Symbol,
+    B: Backend<S, T> + Resolver<S, T>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.dedup.len()
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&T> {
+        Backend::resolve(&self.backend, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T> {
+        Interner::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T {
+        Backend::resolve_unchecked(&self.backend, symbol)
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_> {
+        Resolver::iter(&self.backend)
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::concurrent::ConcurrentStringInterner;
+
+#[cfg(feature = "std")]
+mod concurrent {
+    use super::make_hash;
+    use crate::{
+        compat::{
+            hash_map::RawEntryMut,
+            Box,
+            DefaultHashBuilder,
+            HashMap,
+            Vec,
+        },
+        DefaultSymbol,
+        Symbol,
+    };
+    use core::{
+        fmt,
+        fmt::{
+            Debug,
+            Formatter,
+        },
+        hash::BuildHasher,
+        ptr,
+        slice,
+        sync::atomic::{
+            AtomicPtr,
+            AtomicUsize,
+            Ordering,
+        },
+    };
+    use std::sync::RwLock;
+
+    /// The default number of shards of a [`ConcurrentStringInterner`].
+    const DEFAULT_SHARDS: usize = 32;
+
+    /// Data structure to intern and resolve strings from multiple threads at once.
+    ///
+    /// Unlike the [`StringInterner`](`crate::StringInterner`) all operations take
+    /// `&self` so that the interner can be shared between threads without an
+    /// outer lock.
+    ///
+    /// # Note
+    ///
+    /// The deduplication table is split into shards that are selected by the
+    /// hash of the string. Every shard stores its strings in buckets that are
+    /// never moved, similar to the [`BucketBackend`](`crate::backend::BucketBackend`).
+    /// Symbols are allocated lock-free and resolved through an append-only table
+    /// so that resolved strings stay valid while other threads keep interning.
+    pub struct ConcurrentStringInterner<S = DefaultSymbol, H = DefaultHashBuilder>
+    where
+        S: Symbol,
+        H: BuildHasher,
+    {
+        shards: Box<[RwLock<Shard<S>>]>,
+        spans: Spans,
+        len: AtomicUsize,
+        hasher: H,
+    }
+
+    impl<S, H> Debug for ConcurrentStringInterner<S, H>
+    where
+        S: Symbol,
+        H: BuildHasher,
+    {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ConcurrentStringInterner")
+                .field("shards", &self.shards.len())
+                .field("len", &self.len())
+                .finish()
+        }
+    }
+
+    impl Default for ConcurrentStringInterner {
+        #[cfg_attr(feature = "inline-more", inline)]
+        fn default() -> Self {
+            ConcurrentStringInterner::new()
+        }
+    }
+
+    impl<S, H> ConcurrentStringInterner<S, H>
+    where
+        S: Symbol,
+        H: BuildHasher + Default,
+    {
+        /// Creates a new empty `ConcurrentStringInterner`.
+        #[cfg_attr(feature = "inline-more", inline)]
+        pub fn new() -> Self {
+            Self::with_hasher(Default::default())
+        }
+    }
+
+    impl<S, H> ConcurrentStringInterner<S, H>
+    where
+        S: Symbol,
+        H: BuildHasher,
+    {
+        /// Creates a new empty `ConcurrentStringInterner` with the given hasher.
+        #[cfg_attr(feature = "inline-more", inline)]
+        pub fn with_hasher(hash_builder: H) -> Self {
+            Self::with_shards_and_hasher(DEFAULT_SHARDS, hash_builder)
+        }
+
+        /// Creates a new empty `ConcurrentStringInterner` with the given number
+        /// of shards and the given hasher.
+        ///
+        /// The number of shards is rounded up to the next power of two.
+        pub fn with_shards_and_hasher(shards: usize, hash_builder: H) -> Self {
+            let shards = shards.max(1).next_power_of_two();
+            Self {
+                shards: (0..shards).map(|_| RwLock::new(Shard::default())).collect(),
+                spans: Spans::default(),
+                len: AtomicUsize::new(0),
+                hasher: hash_builder,
+            }
+        }
+
+        /// Returns the number of strings interned by the interner.
+        ///
+        /// # Note
+        ///
+        /// Strings that are concurrently interned by other threads might already
+        /// be accounted for before their symbols are returned.
+        #[cfg_attr(feature = "inline-more", inline)]
+        pub fn len(&self) -> usize {
+            self.len.load(Ordering::Acquire)
+        }
+
+        /// Returns `true` if the string interner has no interned strings.
+        #[cfg_attr(feature = "inline-more", inline)]
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the shard that is responsible for strings with the given hash.
+        fn shard(&self, hash: u64) -> &RwLock<Shard<S>> {
+            // The low bits of the hash select the bucket within a shard's table
+            // and its top 7 bits are used as control bytes so we take neither.
+            let index = (hash >> 40) as usize & (self.shards.len() - 1);
+            &self.shards[index]
+        }
+
+        /// Returns the symbol for the given string if any.
+        ///
+        /// Can be used to query if a string has already been interned without interning.
+        #[inline]
+        pub fn get<T>(&self, string: T) -> Option<S>
+        where
+            T: AsRef<str>,
+        {
+            let string = string.as_ref();
+            let hash = make_hash(&self.hasher, string);
+            self.shard(hash)
+                .read()
+                .unwrap_or_else(|error| error.into_inner())
+                .get(&self.spans, hash, string)
+        }
+
+        /// Interns the given string.
+        ///
+        /// This is used as backend by [`get_or_intern`][1] and [`get_or_intern_static`][2].
+        ///
+        /// [1]: [`ConcurrentStringInterner::get_or_intern`]
+        /// [2]: [`ConcurrentStringInterner::get_or_intern_static`]
+        #[cfg_attr(feature = "inline-more", inline)]
+        fn get_or_intern_using<T>(
+            &self,
+            string: T,
+            alloc_fn: fn(&mut Arena, T) -> *const u8,
+        ) -> S
+        where
+            T: Copy + AsRef<str>,
+        {
+            let str = string.as_ref();
+            let hash = make_hash(&self.hasher, str);
+            let shard = self.shard(hash);
+            // Most look-ups hit already interned strings so we try to get away
+            // with a shared lock first.
+            if let Some(symbol) = shard
+                .read()
+                .unwrap_or_else(|error| error.into_inner())
+                .get(&self.spans, hash, str)
+            {
+                return symbol
+            }
+            let mut shard = shard.write().unwrap_or_else(|error| error.into_inner());
+            let Shard { dedup, arena } = &mut *shard;
+            let Self {
+                spans, len, hasher, ..
+            } = self;
+            let entry = dedup.raw_entry_mut().from_hash(hash, |symbol| {
+                // SAFETY: This is safe because symbols are only inserted into
+                //         a shard after their spans have been written.
+                str == unsafe { spans.resolve_unchecked(symbol.to_usize()) }
+            });
+            let (&mut symbol, &mut ()) = match entry {
+                RawEntryMut::Occupied(occupied) => occupied.into_key_value(),
+                RawEntryMut::Vacant(vacant) => {
+                    let index = len.fetch_add(1, Ordering::AcqRel);
+                    let symbol = match S::try_from_usize(index) {
+                        Some(symbol) => symbol,
+                        None => {
+                            len.fetch_sub(1, Ordering::AcqRel);
+                            panic!("encountered invalid symbol")
+                        }
+                    };
+                    let ptr = alloc_fn(arena, string);
+                    spans.write(index, ptr, str.len());
+                    vacant.insert_with_hasher(hash, symbol, (), |symbol| {
+                        // SAFETY: This is safe because symbols are only inserted into
+                        //         a shard after their spans have been written.
+                        let string =
+                            unsafe { spans.resolve_unchecked(symbol.to_usize()) };
+                        make_hash(hasher, string)
+                    })
+                }
+            };
+            symbol
+        }
+
+        /// Interns the given string.
+        ///
+        /// Returns a symbol for resolution into the original string.
+        ///
+        /// # Panics
+        ///
+        /// If the interner already interns the maximum number of strings possible
+        /// by the chosen symbol type.
+        #[inline]
+        pub fn get_or_intern<T>(&self, string: T) -> S
+        where
+            T: AsRef<str>,
+        {
+            self.get_or_intern_using(string.as_ref(), Arena::alloc)
+        }
+
+        /// Interns the given `'static` string.
+        ///
+        /// Returns a symbol for resolution into the original string.
+        ///
+        /// # Note
+        ///
+        /// This is more efficient than [`ConcurrentStringInterner::get_or_intern`]
+        /// since the string is not copied into the interner.
+        ///
+        /// # Panics
+        ///
+        /// If the interner already interns the maximum number of strings possible
+        /// by the chosen symbol type.
+        #[inline]
+        pub fn get_or_intern_static(&self, string: &'static str) -> S {
+            self.get_or_intern_using(string, Arena::alloc_static)
+        }
+
+        /// Returns the string for the given symbol if any.
+        #[inline]
+        pub fn resolve(&self, symbol: S) -> Option<&str> {
+            self.spans.resolve(symbol.to_usize())
+        }
+    }
+
+    /// A single shard of the [`ConcurrentStringInterner`].
+    #[derive(Debug)]
+    struct Shard<S> {
+        dedup: HashMap<S, (), ()>,
+        arena: Arena,
+    }
+
+    impl<S> Default for Shard<S> {
+        #[cfg_attr(feature = "inline-more", inline)]
+        fn default() -> Self {
+            Self {
+                dedup: HashMap::default(),
+                arena: Arena::default(),
+            }
+        }
+    }
+
+    impl<S> Shard<S>
+    where
+        S: Symbol,
+    {
+        /// Returns the symbol for the given string with the given hash if any.
+        fn get(&self, spans: &Spans, hash: u64, string: &str) -> Option<S> {
+            self.dedup
+                .raw_entry()
+                .from_hash(hash, |symbol| {
+                    // SAFETY: This is safe because symbols are only inserted into
+                    //         a shard after their spans have been written.
+                    string == unsafe { spans.resolve_unchecked(symbol.to_usize()) }
+                })
+                .map(|(&symbol, &())| symbol)
+        }
+    }
+
+    /// Bucket storage for the strings of a single shard.
+    ///
+    /// Strings are never moved once they have been pushed into a bucket.
+    #[derive(Debug, Default)]
+    struct Arena {
+        head: Vec<u8>,
+        full: Vec<Vec<u8>>,
+    }
+
+    impl Arena {
+        /// Copies the given string into the arena and returns a pointer to the copy.
+        fn alloc(&mut self, string: &str) -> *const u8 {
+            let cap = self.head.capacity();
+            if cap < self.head.len() + string.len() {
+                let new_cap = (usize::max(cap, string.len()) + 1).next_power_of_two();
+                let new_head = Vec::with_capacity(new_cap);
+                let old_head = core::mem::replace(&mut self.head, new_head);
+                self.full.push(old_head);
+            }
+            let len = self.head.len();
+            // SAFETY: - There is enough spare capacity in the head as checked above.
+            //         - We only write into the spare capacity through a raw pointer
+            //           so strings resolved by other threads are never aliased.
+            unsafe {
+                let dst = self.head.as_mut_ptr().add(len);
+                ptr::copy_nonoverlapping(string.as_ptr(), dst, string.len());
+                self.head.set_len(len + string.len());
+                dst
+            }
+        }
+
+        /// Returns a pointer to the given static string without copying it.
+        fn alloc_static(&mut self, string: &'static str) -> *const u8 {
+            string.as_ptr()
+        }
+    }
+
+    /// The number of spans in the first segment as a power of two.
+    const FIRST_SEGMENT_LEN_LOG2: u32 = 6;
+
+    /// The number of spans in the first segment.
+    const FIRST_SEGMENT_LEN: usize = 1 << FIRST_SEGMENT_LEN_LOG2;
+
+    /// The maximum number of segments.
+    const SEGMENTS: usize = (usize::BITS - FIRST_SEGMENT_LEN_LOG2) as usize;
+
+    /// A span to an interned string that is written at most once.
+    #[derive(Debug, Default)]
+    struct Span {
+        ptr: AtomicPtr<u8>,
+        len: AtomicUsize,
+    }
+
+    /// Append-only lock-free table mapping symbol indices to interned strings.
+    ///
+    /// The table is made of segments that double in size and are never moved
+    /// or freed until the table is dropped.
+    #[derive(Debug)]
+    struct Spans {
+        segments: [AtomicPtr<Span>; SEGMENTS],
+    }
+
+    impl Default for Spans {
+        fn default() -> Self {
+            Self {
+                segments: core::array::from_fn(|_| AtomicPtr::new(ptr::null_mut())),
+            }
+        }
+    }
+
+    impl Drop for Spans {
+        fn drop(&mut self) {
+            for (segment, ptr) in self.segments.iter_mut().enumerate() {
+                let ptr = *ptr.get_mut();
+                if !ptr.is_null() {
+                    let len = Self::segment_len(segment);
+                    // SAFETY: Non-null segments have been allocated as boxed
+                    //         slices of exactly this length.
+                    drop(unsafe {
+                        Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len))
+                    });
+                }
+            }
+        }
+    }
+
+    impl Spans {
+        /// Returns the number of spans in the given segment.
+        fn segment_len(segment: usize) -> usize {
+            FIRST_SEGMENT_LEN << segment
+        }
+
+        /// Returns the segment and the offset within it for the given index.
+        fn locate(index: usize) -> Option<(usize, usize)> {
+            let biased = index.checked_add(FIRST_SEGMENT_LEN)?;
+            let segment =
+                (usize::BITS - 1 - biased.leading_zeros() - FIRST_SEGMENT_LEN_LOG2)
+                    as usize;
+            Some((segment, biased - Self::segment_len(segment)))
+        }
+
+        /// Returns the span at the given index if its segment has been allocated.
+        fn span(&self, index: usize) -> Option<&Span> {
+            let (segment, offset) = Self::locate(index)?;
+            let ptr = self.segments[segment].load(Ordering::Acquire);
+            if ptr.is_null() {
+                return None
+            }
+            // SAFETY: The offset is within the bounds of its segment.
+            Some(unsafe { &*ptr.add(offset) })
+        }
+
+        /// Returns the span at the given index allocating its segment if needed.
+        fn span_or_alloc(&self, index: usize) -> &Span {
+            let (segment, offset) =
+                Self::locate(index).expect("encountered invalid span index");
+            let slot = &self.segments[segment];
+            let mut ptr = slot.load(Ordering::Acquire);
+            if ptr.is_null() {
+                let len = Self::segment_len(segment);
+                let new = Box::into_raw(
+                    (0..len).map(|_| Span::default()).collect::<Box<[Span]>>(),
+                ) as *mut Span;
+                ptr = match slot.compare_exchange(
+                    ptr::null_mut(),
+                    new,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => new,
+                    Err(winner) => {
+                        // SAFETY: Another thread was faster so we free our own
+                        //         segment that has never been shared.
+                        drop(unsafe {
+                            Box::from_raw(ptr::slice_from_raw_parts_mut(new, len))
+                        });
+                        winner
+                    }
+                };
+            }
+            // SAFETY: The offset is within the bounds of its segment.
+            unsafe { &*ptr.add(offset) }
+        }
+
+        /// Publishes the interned string at the given index.
+        fn write(&self, index: usize, ptr: *const u8, len: usize) {
+            let span = self.span_or_alloc(index);
+            span.len.store(len, Ordering::Relaxed);
+            span.ptr.store(ptr as *mut u8, Ordering::Release);
+        }
+
+        /// Returns the interned string at the given index if any.
+        fn resolve(&self, index: usize) -> Option<&str> {
+            let span = self.span(index)?;
+            let ptr = span.ptr.load(Ordering::Acquire);
+            if ptr.is_null() {
+                return None
+            }
+            let len = span.len.load(Ordering::Relaxed);
+            // SAFETY: - Published spans always point to valid utf8 strings that
+            //           are never moved or mutated.
+            //         - The length is published before the pointer.
+            Some(unsafe {
+                core::str::from_utf8_unchecked(slice::from_raw_parts(ptr, len))
+            })
+        }
+
+        /// Returns the interned string at the given index.
+        ///
+        /// # Safety
+        ///
+        /// The span at the given index must have been written before.
+        unsafe fn resolve_unchecked(&self, index: usize) -> &str {
+            self.resolve(index).unwrap_unchecked()
+        }
+    }
+}
@@ -39,6 +39,17 @@
 //! assert_eq!(interner.resolve(sym), Some("Banana"));
 //! ```
 //!
+//! ### Example: Byte Strings
+//!
+//! ```
+//! use string_interner::BytesInterner;
+//!
+//! let mut interner = BytesInterner::default();
+//! let sym = interner.get_or_intern(&[0xFF, 0xFE][..]);
+//! assert_eq!(interner.get_or_intern(b"\xFF\xFE"), sym);
+//! assert_eq!(interner.resolve(sym), Some(&[0xFF, 0xFE][..]));
+//! ```
+//!
 //! ### Example: Iteration
 //!
 //! ```
@@ -55,17 +66,68 @@
 mod serde_impl;
 
 pub mod backend;
+mod branded;
+mod byte_str;
 mod compat;
+pub mod dedup;
+mod entry;
+mod error;
+mod frozen;
+#[cfg(feature = "global")]
+pub mod global;
 mod interner;
+pub mod remap;
+mod resolver;
+pub mod snapshot;
+mod stats;
 pub mod symbol;
 
+#[cfg(feature = "global")]
+#[doc(inline)]
+pub use self::global::GlobalSymbol;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use self::interner::ConcurrentStringInterner;
+#[cfg(feature = "serde-1")]
+#[doc(inline)]
+pub use self::serde_impl::with_symbols;
 #[doc(inline)]
 pub use self::{
     backend::DefaultBackend,
+    branded::{
+        BrandedInterner,
+        BrandedSymbol,
+    },
+    byte_str::ByteStr,
     compat::DefaultHashBuilder,
-    interner::StringInterner,
+    entry::{
+        Entry,
+        VacantEntry,
+    },
+    error::InternerError,
+    frozen::{
+        FrozenInterner,
+        FrozenResolver,
+    },
+    interner::{
+        BytesInterner,
+        Interner,
+        StringInterner,
+    },
+    remap::SymbolRemap,
+    resolver::Resolver,
+    snapshot::{
+        Snapshot,
+        SnapshotError,
+    },
+    stats::{
+        BackendMemoryStats,
+        MemoryStats,
+    },
     symbol::{
         DefaultSymbol,
         Symbol,
     },
 };
+#[cfg(feature = "derive")]
+pub use string_interner_derive::Symbol;
@@ -0,0 +1,279 @@
+//! Translation tables between the symbols of two interners.
+
+use crate::{
+    backend::Backend,
+    compat::{
+        hash_map,
+        DefaultHashBuilder,
+        HashMap,
+        Vec,
+    },
+    dedup::DedupKey,
+    ByteStr,
+    Interner,
+    Symbol,
+};
+use core::{
+    hash::BuildHasher,
+    iter::Enumerate,
+    marker::PhantomData,
+    slice,
+};
+
+/// Maps the symbols of one interner to the symbols of another interner.
+///
+/// The table is dense over the indices of the source symbols as long as they
+/// are dense and falls back to a hash map for sparse source symbols, e.g. the
+/// generation tagged symbols of a [`FreeListBackend`](`crate::backend::FreeListBackend`)
+/// after strings have been removed.
+///
+/// Created via [`Interner::merge_from`](`crate::Interner::merge_from`).
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::StringInterner;
+/// let mut global = StringInterner::default();
+/// global.get_or_intern("foo");
+/// let mut local = StringInterner::default();
+/// let bar = local.get_or_intern("bar");
+/// let foo = local.get_or_intern("foo");
+/// let remap = global.merge_from(&local);
+/// assert_eq!(global.resolve(remap.apply(bar)), Some("bar"));
+/// assert_eq!(global.get("foo"), Some(remap.apply(foo)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolRemap<From, To = From> {
+    /// The target symbols indexed by their source symbols.
+    map: Table<To>,
+    /// The number of mapped source symbols.
+    len: usize,
+    /// The number of source symbols the remap has been created for.
+    capacity: usize,
+    marker: PhantomData<fn(From) -> To>,
+}
+
+/// The storage of the target symbols of a [`SymbolRemap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Table<To> {
+    /// Target symbols indexed by the `usize` representation of their source symbols.
+    Dense(Vec<Option<To>>),
+    /// Target symbols keyed by the `usize` representation of their source symbols.
+    Sparse(HashMap<usize, To, DefaultHashBuilder>),
+}
+
+impl<From, To> SymbolRemap<From, To>
+where
+    From: Symbol,
+    To: Symbol,
+{
+    /// Creates a new empty remap with space for the given number of symbols.
+    #[inline]
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            map: Table::Dense(Vec::with_capacity(capacity)),
+            len: 0,
+            capacity,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the dense table may grow to hold the given index.
+    ///
+    /// Bounds the dense table by twice the number of expected source symbols
+    /// so that sparse source symbols cannot blow up its size.
+    #[inline]
+    fn fits_dense(&self, index: usize) -> bool {
+        let expected = usize::max(self.capacity, self.len + 1);
+        index < expected.saturating_mul(2).saturating_add(16)
+    }
+
+    /// Maps the source symbol to the target symbol.
+    #[inline]
+    pub(crate) fn insert(&mut self, from: From, to: To) {
+        let index = from.to_usize();
+        if let Table::Dense(map) = &self.map {
+            if index >= map.len() && !self.fits_dense(index) {
+                let sparse = map
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, to)| to.map(|to| (index, to)))
+                    .collect();
+                self.map = Table::Sparse(sparse);
+            }
+        }
+        let previous = match &mut self.map {
+            Table::Dense(map) => {
+                if index >= map.len() {
+                    map.resize(index + 1, None);
+                }
+                map[index].replace(to)
+            }
+            Table::Sparse(map) => map.insert(index, to),
+        };
+        if previous.is_none() {
+            self.len += 1;
+        }
+    }
+
+    /// Returns the number of mapped symbols.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no symbols are mapped.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the target symbol for the given source symbol if any.
+    ///
+    /// Source symbols that encode their string inline are never stored by the
+    /// source interner. They map to the target symbol that encodes the same
+    /// string inline, or to `None` if the target symbol type cannot encode it.
+    #[inline]
+    pub fn get(&self, symbol: From) -> Option<To> {
+        if let Some(string) = symbol.inline_str() {
+            return To::try_inline(string)
+        }
+        let index = symbol.to_usize();
+        match &self.map {
+            Table::Dense(map) => map.get(index).copied().flatten(),
+            Table::Sparse(map) => map.get(&index).copied(),
+        }
+    }
+
+    /// Returns the target symbol for the given source symbol.
+    ///
+    /// # Panics
+    ///
+    /// If the source symbol is not mapped. This includes source symbols that
+    /// encode their string inline if the target symbol type cannot encode it,
+    /// use [`SymbolRemap::apply_or_intern`] for those.
+    #[inline]
+    pub fn apply(&self, symbol: From) -> To {
+        self.get(symbol)
+            .expect("encountered symbol that is not mapped by the remap")
+    }
+
+    /// Returns the target symbol for the given source symbol.
+    ///
+    /// Unlike [`SymbolRemap::apply`] this interns the string of source symbols
+    /// that encode their string inline into the target interner if the target
+    /// symbol type cannot encode it inline as well.
+    ///
+    /// # Panics
+    ///
+    /// If the source symbol is not mapped and does not encode its string inline.
+    #[inline]
+    pub fn apply_or_intern<T, B, H, K>(
+        &self,
+        interner: &mut Interner<T, To, B, H, K>,
+        symbol: From,
+    ) -> To
+    where
+        T: ?Sized + ByteStr,
+        B: Backend<To, T>,
+        H: BuildHasher,
+        K: DedupKey<To>,
+    {
+        if let Some(to) = self.get(symbol) {
+            return to
+        }
+        let string = symbol
+            .inline_str()
+            .expect("encountered symbol that is not mapped by the remap");
+        // SAFETY: `ByteStr` types accept any valid UTF-8.
+        interner.get_or_intern_ref(unsafe { T::from_bytes_unchecked(string.as_bytes()) })
+    }
+
+    /// Returns `true` if every source symbol maps to a target symbol with the same index.
+    ///
+    /// In this case the source symbols can be used with the target interner as is.
+    pub fn is_identity(&self) -> bool {
+        self.iter()
+            .all(|(from, to)| from.to_usize() == to.to_usize())
+    }
+
+    /// Returns an iterator over all pairs of source and target symbols.
+    ///
+    /// The pairs are in order of their source symbols unless these are sparse.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, From, To> {
+        let iter = match &self.map {
+            Table::Dense(map) => IterInner::Dense(map.iter().enumerate()),
+            Table::Sparse(map) => IterInner::Sparse(map.iter()),
+        };
+        Iter {
+            iter,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<S> SymbolRemap<S>
+where
+    S: Symbol,
+{
+    /// Replaces every symbol of the slice by its target symbol.
+    ///
+    /// # Panics
+    ///
+    /// If any symbol of the slice is not mapped.
+    pub fn apply_in_place(&self, symbols: &mut [S]) {
+        for symbol in symbols {
+            *symbol = self.apply(*symbol);
+        }
+    }
+}
+
+impl<'a, From, To> IntoIterator for &'a SymbolRemap<From, To>
+where
+    From: Symbol,
+    To: Symbol,
+{
+    type Item = (From, To);
+    type IntoIter = Iter<'a, From, To>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the pairs of source and target symbols of a [`SymbolRemap`].
+#[derive(Debug)]
+pub struct Iter<'a, From, To> {
+    iter: IterInner<'a, To>,
+    marker: PhantomData<fn() -> From>,
+}
+
+/// The iterator over the [`Table`] of a [`SymbolRemap`].
+#[derive(Debug)]
+enum IterInner<'a, To> {
+    Dense(Enumerate<slice::Iter<'a, Option<To>>>),
+    Sparse(hash_map::Iter<'a, usize, To>),
+}
+
+impl<'a, From, To> Iterator for Iter<'a, From, To>
+where
+    From: Symbol,
+    To: Symbol,
+{
+    type Item = (From, To);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // The indices have been computed from valid source symbols.
+        match &mut self.iter {
+            IterInner::Dense(iter) => {
+                iter.find_map(|(index, to)| Some((From::try_from_usize(index)?, (*to)?)))
+            }
+            IterInner::Sparse(iter) => {
+                iter.find_map(|(&index, &to)| Some((From::try_from_usize(index)?, to)))
+            }
+        }
+    }
+}
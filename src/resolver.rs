@@ -0,0 +1,71 @@
+//! Types that can only turn symbols back into their strings.
+
+use crate::compat::Box;
+
+/// Resolves symbols into their strings.
+///
+/// Implemented by all backends, interners, frozen interners and snapshots so
+/// that code that only resolves symbols does not need to be generic over the
+/// full interner type and can take a `&dyn Resolver<S>` instead.
+///
+/// # Example
+///
+/// ```
+/// use string_interner::{DefaultSymbol, Resolver, StringInterner};
+///
+/// fn render(resolver: &dyn Resolver<DefaultSymbol>, symbol: DefaultSymbol) -> String {
+///     format!("`{}`", resolver.resolve(symbol).unwrap_or("<unknown>"))
+/// }
+///
+/// let mut interner = StringInterner::default();
+/// let foo = interner.get_or_intern("foo");
+/// assert_eq!(render(&interner, foo), "`foo`");
+/// ```
+pub trait Resolver<S, T: ?Sized = str> {
+    /// Returns the number of resolvable strings.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no resolvable strings.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Resolves the given symbol to its original string contents.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` for symbols that encode their string inline since the
+    /// string cannot be borrowed from a symbol that is passed by value.
+    /// Use [`Resolver::resolve_ref`] to resolve those.
+    fn resolve(&self, symbol: S) -> Option<&T>;
+
+    /// Resolves the given symbol to its original string contents.
+    ///
+    /// Unlike [`Resolver::resolve`] this borrows the symbol and thus also resolves
+    /// symbols that encode their string inline.
+    ///
+    /// # Note
+    ///
+    /// The default implementation forwards to [`Resolver::resolve`] and thus
+    /// never resolves inline symbols.
+    #[inline]
+    fn resolve_ref<'a>(&'a self, symbol: &'a S) -> Option<&'a T>
+    where
+        S: Copy,
+    {
+        self.resolve(*symbol)
+    }
+
+    /// Resolves the given symbol to its original string contents.
+    ///
+    /// # Safety
+    ///
+    /// Does not perform validity checks on the given symbol and relies
+    /// on the caller to be provided with a symbol that has been generated
+    /// by the same resolver and that does not encode its string inline.
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &T;
+
+    /// Returns an iterator over all symbols and their strings.
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &T)> + '_>;
+}
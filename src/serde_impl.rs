@@ -1,12 +1,17 @@
 use crate::{
     backend::Backend,
-    StringInterner,
+    compat::Box,
+    dedup::DedupKey,
+    Interner,
     Symbol,
 };
 use core::{
     default::Default,
     fmt,
-    hash::BuildHasher,
+    hash::{
+        BuildHasher,
+        Hash,
+    },
     marker,
 };
 use serde::{
@@ -23,16 +28,18 @@ use serde::{
     },
 };
 
-impl<S, B, H> Serialize for StringInterner<S, B, H>
+impl<T, S, B, H, K> Serialize for Interner<T, S, B, H, K>
 where
+    T: ?Sized + Hash + Eq + Serialize,
     S: Symbol,
-    B: Backend<S>,
-    for<'a> &'a B: IntoIterator<Item = (S, &'a str)>,
+    B: Backend<S, T>,
+    for<'a> &'a B: IntoIterator<Item = (S, &'a T)>,
     H: BuildHasher,
+    K: DedupKey<S>,
 {
-    fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error>
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
     where
-        T: Serializer,
+        Ser: Serializer,
     {
         let mut seq = serializer.serialize_seq(Some(self.len()))?;
         for (_symbol, string) in self {
@@ -42,13 +49,16 @@ where
     }
 }
 
-impl<'de, S, B, H> Deserialize<'de> for StringInterner<S, B, H>
+impl<'de, T, S, B, H, K> Deserialize<'de> for Interner<T, S, B, H, K>
 where
+    T: ?Sized + Hash + Eq,
+    Box<T>: Deserialize<'de>,
     S: Symbol,
-    B: Backend<S>,
+    B: Backend<S, T>,
     H: BuildHasher + Default,
+    K: DedupKey<S>,
 {
-    fn deserialize<D>(deserializer: D) -> Result<StringInterner<S, B, H>, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Interner<T, S, B, H, K>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -56,52 +66,305 @@ where
     }
 }
 
-struct StringInternerVisitor<S, B, H>
+struct StringInternerVisitor<T, S, B, H, K>
 where
+    T: ?Sized,
     S: Symbol,
-    B: Backend<S>,
+    B: Backend<S, T>,
     H: BuildHasher,
+    K: DedupKey<S>,
 {
-    mark: marker::PhantomData<(S, B, H)>,
+    mark: marker::PhantomData<(S, B, H, K)>,
+    value_mark: marker::PhantomData<fn(&T)>,
 }
 
-impl<S, B, H> Default for StringInternerVisitor<S, B, H>
+impl<T, S, B, H, K> Default for StringInternerVisitor<T, S, B, H, K>
 where
+    T: ?Sized,
     S: Symbol,
-    B: Backend<S>,
+    B: Backend<S, T>,
     H: BuildHasher,
+    K: DedupKey<S>,
 {
     fn default() -> Self {
         StringInternerVisitor {
             mark: marker::PhantomData,
+            value_mark: marker::PhantomData,
         }
     }
 }
 
-impl<'de, S, B, H> Visitor<'de> for StringInternerVisitor<S, B, H>
+impl<'de, T, S, B, H, K> Visitor<'de> for StringInternerVisitor<T, S, B, H, K>
 where
+    T: ?Sized + Hash + Eq,
+    Box<T>: Deserialize<'de>,
     S: Symbol,
-    B: Backend<S>,
+    B: Backend<S, T>,
     H: BuildHasher + Default,
+    K: DedupKey<S>,
 {
-    type Value = StringInterner<S, B, H>;
+    type Value = Interner<T, S, B, H, K>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("Expected a contiguous sequence of strings.")
     }
 
-    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
-        let mut interner: StringInterner<S, B, H> =
-            StringInterner::with_capacity_and_hasher(
-                seq.size_hint().unwrap_or(0),
-                H::default(),
-            );
-        while let Some(s) = seq.next_element::<Box<str>>()? {
-            interner.get_or_intern(s);
+        let mut interner: Interner<T, S, B, H, K> = Interner::with_hasher(H::default());
+        let mut strings = SeqStrings {
+            seq,
+            error: None,
+            marker: marker::PhantomData,
+        };
+        interner.extend(&mut strings);
+        match strings.error {
+            Some(error) => Err(error),
+            None => Ok(interner),
+        }
+    }
+}
+
+/// Yields the strings of a serde sequence so that they can be bulk interned.
+///
+/// Stops at the first error and stores it in `error`.
+struct SeqStrings<'de, A, T: ?Sized>
+where
+    A: SeqAccess<'de>,
+{
+    seq: A,
+    error: Option<A::Error>,
+    marker: marker::PhantomData<fn(&'de ()) -> Box<T>>,
+}
+
+impl<'de, A, T> Iterator for SeqStrings<'de, A, T>
+where
+    A: SeqAccess<'de>,
+    T: ?Sized,
+    Box<T>: Deserialize<'de>,
+{
+    type Item = Box<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None
+        }
+        match self.seq.next_element::<Box<T>>() {
+            Ok(string) => string,
+            Err(error) => {
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.seq.size_hint().unwrap_or(0), None)
+    }
+}
+
+/// Serializes an interner as `(symbol, string)` pairs and checks them on deserialization.
+///
+/// The default [`Serialize`] and [`Deserialize`] implementations of [`Interner`]
+/// store a plain sequence of strings and re-intern them in order. If the input
+/// is tampered with or contains duplicates, symbols handed out before serialization
+/// may silently resolve to different strings afterwards.
+///
+/// This module stores every string together with its symbol instead. Deserialization
+/// fails with a serde error unless the symbols form the dense sequence `0, 1, 2, ..`
+/// and every string is unique, so symbols persisted elsewhere stay valid.
+///
+/// Use it via `#[serde(with = "string_interner::with_symbols")]`.
+///
+/// # Note
+///
+/// This requires a backend that hands out dense symbols, which holds for all
+/// backends provided by this crate unless strings have been removed from a
+/// [`FreeListBackend`](`crate::backend::FreeListBackend`).
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::StringInterner;
+/// let mut interner = StringInterner::default();
+/// let hello = interner.get_or_intern("hello");
+/// let world = interner.get_or_intern("world");
+///
+/// let mut buffer = Vec::new();
+/// let mut serializer = serde_json::Serializer::new(&mut buffer);
+/// string_interner::with_symbols::serialize(&interner, &mut serializer).unwrap();
+/// assert_eq!(buffer, br#"[[0,"hello"],[1,"world"]]"#);
+///
+/// let mut deserializer = serde_json::Deserializer::from_slice(&buffer);
+/// let restored: StringInterner =
+///     string_interner::with_symbols::deserialize(&mut deserializer).unwrap();
+/// assert_eq!(restored.resolve(hello), Some("hello"));
+/// assert_eq!(restored.resolve(world), Some("world"));
+///
+/// let mut deserializer = serde_json::Deserializer::from_slice(br#"[[0,"a"],[2,"b"]]"#);
+/// assert!(string_interner::with_symbols::deserialize::<_, StringInterner>(&mut deserializer).is_err());
+/// ```
+pub mod with_symbols {
+    use super::*;
+    use serde::{
+        de::Error as _,
+        ser::Error as _,
+    };
+
+    /// Serializes the interner as a sequence of `(symbol, string)` pairs.
+    ///
+    /// Symbols are written as their `usize` representation.
+    ///
+    /// # Errors
+    ///
+    /// If the backend does not hand out dense symbols.
+    pub fn serialize<Ser, T, S, B, H, K>(
+        interner: &Interner<T, S, B, H, K>,
+        serializer: Ser,
+    ) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+        T: ?Sized + Hash + Eq + Serialize,
+        S: Symbol,
+        B: Backend<S, T>,
+        H: BuildHasher,
+        K: DedupKey<S>,
+    {
+        let mut seq = serializer.serialize_seq(Some(interner.len()))?;
+        for index in 0..interner.len() {
+            let string = S::try_from_usize(index)
+                .and_then(|symbol| interner.resolve(symbol))
+                .ok_or_else(|| {
+                    Ser::Error::custom(format_args!(
+                        "interner has no string for symbol {}",
+                        index
+                    ))
+                })?;
+            seq.serialize_element(&(index, string))?
+        }
+        seq.end()
+    }
+
+    /// Deserializes an interner from a sequence of `(symbol, string)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// If the symbols are not exactly `0, 1, 2, ..` in order, if a symbol cannot be
+    /// represented by `S`, or if the same string occurs more than once.
+    pub fn deserialize<'de, D, I>(deserializer: D) -> Result<I, D::Error>
+    where
+        D: Deserializer<'de>,
+        I: FromSymbolPairs<'de>,
+    {
+        I::from_symbol_pairs(deserializer)
+    }
+
+    /// Interners that can be deserialized by [`deserialize`].
+    ///
+    /// This is implemented for every [`Interner`] and only exists so that
+    /// [`deserialize`] can be named in `#[serde(with = "..")]` attributes.
+    pub trait FromSymbolPairs<'de>: Sized {
+        /// Deserializes `Self` from a sequence of `(symbol, string)` pairs.
+        fn from_symbol_pairs<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>;
+    }
+
+    impl<'de, T, S, B, H, K> FromSymbolPairs<'de> for Interner<T, S, B, H, K>
+    where
+        T: ?Sized + Hash + Eq,
+        Box<T>: Deserialize<'de>,
+        S: Symbol,
+        B: Backend<S, T>,
+        H: BuildHasher + Default,
+        K: DedupKey<S>,
+    {
+        fn from_symbol_pairs<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(SymbolPairsVisitor::<T, S, B, H, K>::default())
+        }
+    }
+
+    struct SymbolPairsVisitor<T, S, B, H, K>
+    where
+        T: ?Sized,
+    {
+        mark: marker::PhantomData<(S, B, H, K)>,
+        value_mark: marker::PhantomData<fn(&T)>,
+    }
+
+    impl<T, S, B, H, K> Default for SymbolPairsVisitor<T, S, B, H, K>
+    where
+        T: ?Sized,
+    {
+        fn default() -> Self {
+            SymbolPairsVisitor {
+                mark: marker::PhantomData,
+                value_mark: marker::PhantomData,
+            }
+        }
+    }
+
+    impl<'de, T, S, B, H, K> Visitor<'de> for SymbolPairsVisitor<T, S, B, H, K>
+    where
+        T: ?Sized + Hash + Eq,
+        Box<T>: Deserialize<'de>,
+        S: Symbol,
+        B: Backend<S, T>,
+        H: BuildHasher + Default,
+        K: DedupKey<S>,
+    {
+        type Value = Interner<T, S, B, H, K>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a dense sequence of (symbol, string) pairs")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut interner: Interner<T, S, B, H, K> =
+                Interner::with_capacity_and_hasher(
+                    seq.size_hint().unwrap_or(0),
+                    H::default(),
+                );
+            while let Some((index, string)) = seq.next_element::<(usize, Box<T>)>()? {
+                let symbol = S::try_from_usize(index).ok_or_else(|| {
+                    A::Error::custom(format_args!(
+                        "symbol {} is out of range for the symbol type",
+                        index
+                    ))
+                })?;
+                let expected = interner.len();
+                if index < expected {
+                    return Err(A::Error::custom(format_args!(
+                        "duplicate symbol {}, expected symbol {}",
+                        index, expected
+                    )))
+                }
+                if index > expected {
+                    return Err(A::Error::custom(format_args!(
+                        "gap in symbols: found symbol {}, expected symbol {}",
+                        index, expected
+                    )))
+                }
+                let interned = interner
+                    .try_get_or_intern(string)
+                    .map_err(A::Error::custom)?;
+                if interned != symbol {
+                    return Err(A::Error::custom(format_args!(
+                        "duplicate string for symbol {}, already interned as symbol {}",
+                        index,
+                        interned.to_usize()
+                    )))
+                }
+            }
+            Ok(interner)
         }
-        Ok(interner)
     }
 }
@@ -0,0 +1,484 @@
+//! A versioned binary snapshot format for string interners.
+//!
+//! Snapshots store the interned strings in the layout of the
+//! [`StringBackend`](`crate::backend::StringBackend`), i.e. one contiguous
+//! buffer plus the end offsets of all strings, together with a prebuilt hash
+//! index. Loading a snapshot validates it once and afterwards serves
+//! [`Snapshot::get`] and [`Snapshot::resolve`] directly from the borrowed bytes
+//! without copying or rehashing any of the strings.
+//!
+//! Since a [`Snapshot`] only borrows its bytes, large snapshots can be loaded
+//! lazily by memory-mapping the file, e.g. with the `memmap2` crate, and passing
+//! the mapped bytes to [`Snapshot::from_bytes`].
+//!
+//! # Layout
+//!
+//! All integers are encoded as little-endian `u32`.
+//!
+//! | Field    | Size in bytes    | Description                                          |
+//! |----------|------------------|------------------------------------------------------|
+//! | magic    | 8                | [`Snapshot::MAGIC`]                                  |
+//! | version  | 4                | [`Snapshot::VERSION`]                                |
+//! | len      | 4                | number of interned strings                           |
+//! | slots    | 4                | number of hash index slots, a power of two           |
+//! | size     | 4                | size of the string buffer in bytes                   |
+//! | ends     | 4 * len          | end offset into the buffer of every string           |
+//! | index    | 4 * slots        | open addressing hash table: symbol index + 1, or `0` |
+//! | buffer   | size             | all strings concatenated, UTF-8 encoded              |
+//!
+//! The hash index uses 64-bit FNV-1a with linear probing so that snapshots
+//! are independent of the hasher used by the interner that wrote them.
+
+use crate::{
+    backend::Backend,
+    compat::{
+        vec,
+        Box,
+        Vec,
+    },
+    dedup::DedupKey,
+    Interner,
+    Resolver,
+    Symbol,
+};
+use core::{
+    convert::TryFrom,
+    fmt,
+    hash::BuildHasher,
+    iter::Enumerate,
+    marker::PhantomData,
+    slice::ChunksExact,
+    str,
+};
+
+/// Size of the snapshot header in bytes.
+const HEADER_LEN: usize = 24;
+
+/// Errors that may occur when writing or loading a snapshot.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SnapshotError {
+    /// The data does not start with [`Snapshot::MAGIC`].
+    InvalidMagic,
+    /// The snapshot was written in an unsupported format version.
+    UnsupportedVersion(u32),
+    /// The data ends before all sections of the snapshot.
+    Truncated,
+    /// The offsets or the hash index of the snapshot are inconsistent.
+    InvalidLayout,
+    /// The string buffer of the snapshot is not valid UTF-8.
+    InvalidUtf8,
+    /// The symbol type cannot represent all strings of the snapshot.
+    SymbolsExhausted,
+    /// The interner is too large to be written as a snapshot.
+    TooLarge,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => f.write_str("not a string interner snapshot"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            Self::Truncated => f.write_str("snapshot data is truncated"),
+            Self::InvalidLayout => f.write_str("snapshot layout is invalid"),
+            Self::InvalidUtf8 => f.write_str("snapshot strings are not valid UTF-8"),
+            Self::SymbolsExhausted => f.write_str("ran out of symbols"),
+            Self::TooLarge => f.write_str("interner is too large for a snapshot"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotError {}
+
+/// Computes the 64-bit FNV-1a hash of the given bytes.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Returns the number of hash index slots for `len` strings.
+///
+/// Keeps the load factor at or below 50% and always leaves an empty slot.
+fn slots_for(len: usize) -> Option<usize> {
+    len.checked_mul(2)?
+        .checked_add(1)?
+        .checked_next_power_of_two()
+}
+
+/// Reads the `index`-th little-endian `u32` of `bytes`.
+#[inline]
+fn read_u32(bytes: &[u8], index: usize) -> u32 {
+    let offset = index * 4;
+    let mut value = [0; 4];
+    value.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(value)
+}
+
+/// Splits `len` bytes off the front of `bytes`.
+fn split<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], SnapshotError> {
+    if bytes.len() < len {
+        return Err(SnapshotError::Truncated)
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+impl<S, B, H, K> Interner<str, S, B, H, K>
+where
+    S: Symbol,
+    B: Backend<S, str>,
+    H: BuildHasher,
+    K: DedupKey<S>,
+{
+    /// Writes the interned strings into a new snapshot.
+    ///
+    /// The snapshot can be loaded via [`Snapshot::from_bytes`] and resolves the
+    /// same symbols to the same strings as `self`.
+    ///
+    /// # Errors
+    ///
+    /// If the interner holds more than `u32::MAX` bytes or strings.
+    ///
+    /// # Note
+    ///
+    /// This requires a backend that hands out dense symbols, which holds for all
+    /// backends provided by this crate unless strings have been removed from a
+    /// [`FreeListBackend`](`crate::backend::FreeListBackend`).
+    pub fn to_snapshot(&self) -> Result<Vec<u8>, SnapshotError> {
+        let len = self.len();
+        let strings = (0..len)
+            .map(|index| {
+                S::try_from_usize(index)
+                    .and_then(|symbol| self.resolve(symbol))
+                    .ok_or(SnapshotError::InvalidLayout)
+            })
+            .collect::<Result<Vec<&str>, _>>()?;
+        let size = strings.iter().map(|string| string.len()).sum::<usize>();
+        let slots = slots_for(len).ok_or(SnapshotError::TooLarge)?;
+        let to_u32 =
+            |value: usize| u32::try_from(value).map_err(|_| SnapshotError::TooLarge);
+        let mut index = vec![0_u32; slots];
+        for (symbol, string) in strings.iter().enumerate() {
+            let mut slot = fnv1a(string.as_bytes()) as usize & (slots - 1);
+            while index[slot] != 0 {
+                slot = (slot + 1) & (slots - 1);
+            }
+            index[slot] = to_u32(symbol + 1)?;
+        }
+        let mut snapshot = Vec::with_capacity(HEADER_LEN + 4 * (len + slots) + size);
+        snapshot.extend_from_slice(&Snapshot::<S>::MAGIC);
+        snapshot.extend_from_slice(&Snapshot::<S>::VERSION.to_le_bytes());
+        snapshot.extend_from_slice(&to_u32(len)?.to_le_bytes());
+        snapshot.extend_from_slice(&to_u32(slots)?.to_le_bytes());
+        snapshot.extend_from_slice(&to_u32(size)?.to_le_bytes());
+        let mut end = 0;
+        for string in &strings {
+            end += string.len();
+            snapshot.extend_from_slice(&to_u32(end)?.to_le_bytes());
+        }
+        for entry in index {
+            snapshot.extend_from_slice(&entry.to_le_bytes());
+        }
+        for string in &strings {
+            snapshot.extend_from_slice(string.as_bytes());
+        }
+        Ok(snapshot)
+    }
+
+    /// Writes the interned strings as a snapshot into `writer`.
+    ///
+    /// See [`Interner::to_snapshot`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails or the interner is too large for a snapshot.
+    #[cfg(feature = "std")]
+    pub fn write_snapshot<W>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        let snapshot = self.to_snapshot().map_err(|error| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, error)
+        })?;
+        writer.write_all(&snapshot)
+    }
+}
+
+/// A read-only string interner borrowing its data from a binary snapshot.
+///
+/// Snapshots are written via [`Interner::to_snapshot`] and loaded from any byte
+/// slice, for example a memory-mapped file. All validation happens once in
+/// [`Snapshot::from_bytes`]; afterwards look-ups neither copy nor rehash the
+/// stored strings.
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{Snapshot, StringInterner, DefaultSymbol};
+/// let mut interner = StringInterner::default();
+/// let foo = interner.get_or_intern("foo");
+/// let bar = interner.get_or_intern("bar");
+/// let bytes = interner.to_snapshot().unwrap();
+///
+/// let snapshot = Snapshot::<DefaultSymbol>::from_bytes(&bytes).unwrap();
+/// assert_eq!(snapshot.len(), 2);
+/// assert_eq!(snapshot.get("foo"), Some(foo));
+/// assert_eq!(snapshot.get("baz"), None);
+/// assert_eq!(snapshot.resolve(bar), Some("bar"));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Snapshot<'a, S> {
+    ends: &'a [u8],
+    index: &'a [u8],
+    buffer: &'a str,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<'a, S> Snapshot<'a, S>
+where
+    S: Symbol,
+{
+    /// The magic bytes every snapshot starts with.
+    pub const MAGIC: [u8; 8] = *b"STRINTRN";
+
+    /// The snapshot format version written by this crate.
+    pub const VERSION: u32 = 1;
+
+    /// Loads a snapshot from the given bytes.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are not a valid snapshot or if `S` cannot represent all
+    /// of its strings.
+    pub fn from_bytes(mut bytes: &'a [u8]) -> Result<Self, SnapshotError> {
+        let header = split(&mut bytes, HEADER_LEN)?;
+        if header[..8] != Self::MAGIC {
+            return Err(SnapshotError::InvalidMagic)
+        }
+        let header = &header[8..];
+        let version = read_u32(header, 0);
+        if version != Self::VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version))
+        }
+        let len = read_u32(header, 1) as usize;
+        let slots = read_u32(header, 2) as usize;
+        let size = read_u32(header, 3) as usize;
+        if len > 0 && S::try_from_usize(len - 1).is_none() {
+            return Err(SnapshotError::SymbolsExhausted)
+        }
+        if !slots.is_power_of_two() || slots <= len {
+            return Err(SnapshotError::InvalidLayout)
+        }
+        let ends = split(
+            &mut bytes,
+            len.checked_mul(4).ok_or(SnapshotError::Truncated)?,
+        )?;
+        let index = split(
+            &mut bytes,
+            slots.checked_mul(4).ok_or(SnapshotError::Truncated)?,
+        )?;
+        let buffer = split(&mut bytes, size)?;
+        let buffer = str::from_utf8(buffer).map_err(|_| SnapshotError::InvalidUtf8)?;
+        let mut start = 0;
+        for end in ends.chunks_exact(4) {
+            let end = read_u32(end, 0) as usize;
+            if end < start || end > size || !buffer.is_char_boundary(end) {
+                return Err(SnapshotError::InvalidLayout)
+            }
+            start = end;
+        }
+        if start != size {
+            return Err(SnapshotError::InvalidLayout)
+        }
+        let mut occupied = 0;
+        for entry in index.chunks_exact(4) {
+            let entry = read_u32(entry, 0) as usize;
+            if entry > len {
+                return Err(SnapshotError::InvalidLayout)
+            }
+            occupied += (entry != 0) as usize;
+        }
+        if occupied != len {
+            return Err(SnapshotError::InvalidLayout)
+        }
+        Ok(Self {
+            ends,
+            index,
+            buffer,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns the number of strings in the snapshot.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.ends.len() / 4
+    }
+
+    /// Returns `true` if the snapshot contains no strings.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the symbol for the given string if any.
+    ///
+    /// Can be used to query if a string has already been interned without interning.
+    pub fn get<T>(&self, string: T) -> Option<S>
+    where
+        T: AsRef<str>,
+    {
+        let string = string.as_ref();
+        if let Some(symbol) = S::try_inline(string) {
+            return Some(symbol)
+        }
+        let mask = self.index.len() / 4 - 1;
+        let mut slot = fnv1a(string.as_bytes()) as usize & mask;
+        loop {
+            let entry = read_u32(self.index, slot) as usize;
+            if entry == 0 {
+                return None
+            }
+            // SAFETY: All index entries have been checked to be at most `len`
+            //         in `from_bytes`.
+            if unsafe { self.span_to_str(entry - 1) } == string {
+                return S::try_from_usize(entry - 1)
+            }
+            slot = (slot + 1) & mask;
+        }
+    }
+
+    /// Returns the string for the given symbol if any.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&'a str> {
+        let index = symbol.to_usize();
+        if index >= self.len() {
+            return None
+        }
+        // SAFETY: We just checked that `index` is in bounds.
+        Some(unsafe { self.span_to_str(index) })
+    }
+
+    /// Returns the string for the given symbol if any.
+    ///
+    /// Unlike [`Snapshot::resolve`] this also resolves symbols that encode their
+    /// string inline.
+    #[inline]
+    pub fn resolve_ref<'b>(&'b self, symbol: &'b S) -> Option<&'b str> {
+        match symbol.inline_str() {
+            Some(string) => Some(string),
+            None => self.resolve(*symbol),
+        }
+    }
+
+    /// Returns an iterator over all symbols and strings of the snapshot.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, S> {
+        Iter {
+            buffer: self.buffer,
+            start: 0,
+            ends: self.ends.chunks_exact(4).enumerate(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the string at `index`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `index` is less than `self.len()`.
+    #[inline]
+    unsafe fn span_to_str(&self, index: usize) -> &'a str {
+        let start = match index {
+            0 => 0,
+            _ => read_u32(self.ends, index - 1) as usize,
+        };
+        let end = read_u32(self.ends, index) as usize;
+        // SAFETY: All ends have been checked to be ascending char boundaries
+        //         within the buffer in `from_bytes`.
+        unsafe { self.buffer.get_unchecked(start..end) }
+    }
+}
+
+impl<'a, S> Resolver<S> for Snapshot<'a, S>
+where
+    S: Symbol,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        Snapshot::len(self)
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&str> {
+        Snapshot::resolve(self, symbol)
+    }
+
+    #[inline]
+    fn resolve_ref<'b>(&'b self, symbol: &'b S) -> Option<&'b str> {
+        Snapshot::resolve_ref(self, symbol)
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &str {
+        self.span_to_str(symbol.to_usize())
+    }
+
+    #[inline]
+    fn iter(&self) -> Box<dyn Iterator<Item = (S, &str)> + '_> {
+        Box::new(Snapshot::iter(self))
+    }
+}
+
+impl<'a, S> IntoIterator for &Snapshot<'a, S>
+where
+    S: Symbol,
+{
+    type Item = (S, &'a str);
+    type IntoIter = Iter<'a, S>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the symbols and strings of a [`Snapshot`].
+pub struct Iter<'a, S> {
+    buffer: &'a str,
+    start: usize,
+    ends: Enumerate<ChunksExact<'a, u8>>,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<'a, S> Iterator for Iter<'a, S>
+where
+    S: Symbol,
+{
+    type Item = (S, &'a str);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ends.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ends.next().map(|(index, end)| {
+            let end = read_u32(end, 0) as usize;
+            // SAFETY: All ends have been checked to be ascending char boundaries
+            //         within the buffer in `from_bytes`.
+            let string = unsafe { self.buffer.get_unchecked(self.start..end) };
+            self.start = end;
+            let symbol = S::try_from_usize(index)
+                .expect("encountered invalid symbol index for snapshot");
+            (symbol, string)
+        })
+    }
+}
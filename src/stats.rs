@@ -0,0 +1,84 @@
+//! Memory statistics of string interners and their backends.
+
+use core::mem;
+
+/// Memory statistics reported by a [`Backend`](`crate::backend::Backend`).
+///
+/// Returned by [`Backend::memory_stats`](`crate::backend::Backend::memory_stats`).
+/// All sizes are in bytes and only account for heap memory owned by the backend.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BackendMemoryStats {
+    /// The number of bytes used by interned string data.
+    pub string_bytes: usize,
+    /// The number of bytes allocated for string data but not in use.
+    ///
+    /// For the [`BucketBackend`](`crate::backend::BucketBackend`) this is the
+    /// unused capacity of its head and full buckets.
+    pub slack_bytes: usize,
+    /// The number of entries in the per-string index, e.g. spans or ends.
+    pub index_len: usize,
+    /// The capacity of the per-string index, e.g. spans or ends.
+    pub index_capacity: usize,
+    /// The number of bytes allocated for the per-string index and other bookkeeping.
+    pub index_bytes: usize,
+}
+
+impl BackendMemoryStats {
+    /// Returns the total number of heap bytes allocated by the backend.
+    #[inline]
+    pub fn heap_size(&self) -> usize {
+        self.string_bytes + self.slack_bytes + self.index_bytes
+    }
+}
+
+/// Memory statistics reported by an [`Interner`](`crate::Interner`).
+///
+/// Returned by [`Interner::memory_stats`](`crate::Interner::memory_stats`).
+/// All sizes are in bytes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryStats {
+    /// The memory statistics of the backend.
+    pub backend: BackendMemoryStats,
+    /// The number of entries in the deduplication table.
+    pub dedup_len: usize,
+    /// The number of entries the deduplication table can hold without reallocating.
+    pub dedup_capacity: usize,
+    /// The approximate number of bytes allocated by the deduplication table.
+    pub dedup_bytes: usize,
+}
+
+impl MemoryStats {
+    /// Returns the load factor of the deduplication table.
+    ///
+    /// This is the ratio of its entries to its capacity or `0.0` if it is unallocated.
+    #[inline]
+    pub fn dedup_load_factor(&self) -> f64 {
+        if self.dedup_capacity == 0 {
+            return 0.0
+        }
+        self.dedup_len as f64 / self.dedup_capacity as f64
+    }
+
+    /// Returns the total number of heap bytes allocated by the interner.
+    #[inline]
+    pub fn heap_size(&self) -> usize {
+        self.backend.heap_size() + self.dedup_bytes
+    }
+}
+
+/// Returns the approximate number of bytes allocated by a hash table with
+/// the given capacity and entries of type `T`.
+///
+/// Mirrors the table layout of `hashbrown`: a power of two number of buckets
+/// of which at most 7/8 are usable, plus one control byte per bucket and one
+/// trailing group of control bytes.
+pub(crate) fn hash_table_bytes<T>(capacity: usize) -> usize {
+    /// The width of a group of control bytes using SSE2.
+    const GROUP_WIDTH: usize = 16;
+    let buckets = match capacity {
+        0 => return 0,
+        1..=6 => capacity + 1,
+        _ => capacity / 7 * 8,
+    };
+    buckets * (mem::size_of::<T>() + 1) + GROUP_WIDTH
+}
@@ -5,10 +5,16 @@
 //! method returns `Symbol` types that allow to look-up the original string
 //! using [`StringInterner::resolve`](`crate::StringInterner::resolve`).
 
-use core::num::{
-    NonZeroU16,
-    NonZeroU32,
-    NonZeroUsize,
+#[cfg(feature = "backends")]
+use crate::InternerError;
+use core::{
+    convert::TryFrom,
+    num::{
+        NonZeroU16,
+        NonZeroU32,
+        NonZeroU64,
+        NonZeroUsize,
+    },
 };
 
 /// Types implementing this trait can be used as symbols for string interners.
@@ -28,6 +34,31 @@ pub trait Symbol: Copy + Eq {
 
     /// Returns the `usize` representation of `self`.
     fn to_usize(self) -> usize;
+
+    /// The maximum length in bytes of strings that the symbol encodes inline.
+    ///
+    /// Is `0` for symbols that never encode strings inline.
+    const INLINE_CAPACITY: usize = 0;
+
+    /// Creates a symbol that encodes the given string inline.
+    ///
+    /// Returns `None` if the symbol cannot encode the string, which is the
+    /// default. Interners never store strings that can be encoded inline so that
+    /// symbols are equal if and only if their strings are equal.
+    #[inline]
+    fn try_inline(string: &str) -> Option<Self> {
+        let _ = string;
+        None
+    }
+
+    /// Returns the string that `self` encodes inline if any.
+    ///
+    /// Inline symbols resolve without a backend, see
+    /// [`StringInterner::resolve_ref`](`crate::StringInterner::resolve_ref`).
+    #[inline]
+    fn inline_str(&self) -> Option<&str> {
+        None
+    }
 }
 
 /// Creates the symbol `S` from the given `usize`.
@@ -44,6 +75,20 @@ where
     S::try_from_usize(index).expect("encountered invalid symbol")
 }
 
+/// Creates the symbol `S` from the given `usize`.
+///
+/// # Errors
+///
+/// If the conversion is invalid because all symbols are exhausted.
+#[cfg(feature = "backends")]
+#[inline]
+pub(crate) fn try_valid_symbol<S>(index: usize) -> Result<S, InternerError>
+where
+    S: Symbol,
+{
+    S::try_from_usize(index).ok_or(InternerError::SymbolsExhausted)
+}
+
 /// The symbol type that is used by default.
 pub type DefaultSymbol = SymbolU32;
 
@@ -70,10 +115,32 @@ macro_rules! gen_symbol_for {
             value: $non_zero,
         }
 
+        impl $name {
+            /// Creates the symbol for the given index in a `const` context.
+            ///
+            /// This is the `const` counterpart of [`Symbol::try_from_usize`].
+            ///
+            /// # Panics
+            ///
+            /// If the symbol cannot represent the given index.
+            #[inline]
+            pub const fn new(index: usize) -> Self {
+                if index >= <$base_ty>::MAX as usize {
+                    panic!("encountered invalid symbol")
+                }
+                match <$non_zero>::new(index as $base_ty + 1) {
+                    Some(value) => Self { value },
+                    None => panic!("encountered invalid symbol"),
+                }
+            }
+        }
+
         impl Symbol for $name {
             #[inline]
             fn try_from_usize(index: usize) -> Option<Self> {
-                <$non_zero>::new((index as $base_ty).wrapping_add(1))
+                <$base_ty>::try_from(index)
+                    .ok()
+                    .and_then(|index| <$non_zero>::new(index.wrapping_add(1)))
                     .map(|value| Self { value })
             }
 
@@ -103,11 +170,200 @@ gen_symbol_for!(
     struct SymbolUsize(NonZeroUsize; usize);
 );
 
+/// Symbol that is 64-bit in size and encodes strings of up to 7 bytes inline.
+///
+/// Short strings are stored in the symbol itself so that interning them neither
+/// looks them up nor stores them. Longer strings are interned as usual and
+/// refer to their index in the backend.
+///
+/// # Note
+///
+/// Inline symbols cannot be resolved by
+/// [`StringInterner::resolve`](`crate::StringInterner::resolve`) since there is no
+/// string stored in the interner to borrow from. Use
+/// [`StringInterner::resolve_ref`](`crate::StringInterner::resolve_ref`) or
+/// [`InlineSymbolU64::as_str`] instead.
+///
+/// Is space-optimized for used in `Option`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct InlineSymbolU64 {
+    /// In memory order the bytes of an inline string followed by zeros and the
+    /// tag byte, or the little endian index plus one followed by a zero byte.
+    value: NonZeroU64,
+}
+
+impl InlineSymbolU64 {
+    /// The tag bit of the last byte that marks inline symbols.
+    const INLINE_TAG: u8 = 0x80;
+
+    /// Creates the symbol from its bytes in memory order.
+    ///
+    /// Returns `None` if all bytes are zero.
+    #[inline]
+    fn from_bytes(bytes: [u8; 8]) -> Option<Self> {
+        NonZeroU64::new(u64::from_ne_bytes(bytes)).map(|value| Self { value })
+    }
+
+    /// Returns the bytes of the symbol in memory order.
+    #[inline]
+    fn bytes(&self) -> &[u8; 8] {
+        // SAFETY: `NonZeroU64` is as large as `[u8; 8]` which has an alignment
+        //         of 1 and for which every bit pattern is valid.
+        unsafe { &*(&self.value as *const NonZeroU64).cast::<[u8; 8]>() }
+    }
+
+    /// Returns the string that `self` encodes inline if any.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        let bytes = self.bytes();
+        let tag = bytes[7];
+        if tag & Self::INLINE_TAG == 0 {
+            return None
+        }
+        let len = usize::from(tag & !Self::INLINE_TAG);
+        // SAFETY: Inline symbols are only created from the bytes of a `str`
+        //         in `try_inline`.
+        Some(unsafe { core::str::from_utf8_unchecked(&bytes[..len]) })
+    }
+}
+
+impl Symbol for InlineSymbolU64 {
+    const INLINE_CAPACITY: usize = 7;
+
+    #[inline]
+    fn try_from_usize(index: usize) -> Option<Self> {
+        let value = u64::try_from(index).ok()?.checked_add(1)?;
+        if value >> 56 != 0 {
+            return None
+        }
+        Self::from_bytes(value.to_le_bytes())
+    }
+
+    /// Returns the `usize` representation of `self`.
+    ///
+    /// Returns `usize::MAX` for inline symbols which no backend resolves.
+    #[inline]
+    fn to_usize(self) -> usize {
+        let bytes = self.bytes();
+        if bytes[7] != 0 {
+            return usize::MAX
+        }
+        usize::try_from(u64::from_le_bytes(*bytes) - 1).unwrap_or(usize::MAX)
+    }
+
+    #[inline]
+    fn try_inline(string: &str) -> Option<Self> {
+        let len = string.len();
+        if len > Self::INLINE_CAPACITY {
+            return None
+        }
+        let mut bytes = [0; 8];
+        bytes[..len].copy_from_slice(string.as_bytes());
+        bytes[7] = Self::INLINE_TAG | len as u8;
+        Self::from_bytes(bytes)
+    }
+
+    #[inline]
+    fn inline_str(&self) -> Option<&str> {
+        self.as_str()
+    }
+}
+
+impl core::fmt::Debug for InlineSymbolU64 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.as_str() {
+            Some(string) => f.debug_tuple("InlineSymbolU64").field(&string).finish(),
+            None => {
+                f.debug_tuple("InlineSymbolU64")
+                    .field(&self.to_usize())
+                    .finish()
+            }
+        }
+    }
+}
+
+/// Declares a module of symbols that are fixed at compile time.
+///
+/// Generates a `const` [`DefaultSymbol`] for every declared string together with
+/// a `TABLE` of all strings in declaration order. An interner created via
+/// [`Interner::with_prefilled`](`crate::Interner::with_prefilled`) from that
+/// table resolves the generated symbols to their strings.
+///
+/// # Example
+///
+/// ```
+/// use string_interner::{symbols, StringInterner};
+///
+/// symbols! {
+///     /// Keywords of the language.
+///     pub mod kw {
+///         Fn: "fn",
+///         Let: "let",
+///         Struct: "struct",
+///     }
+/// }
+///
+/// let mut interner: StringInterner = StringInterner::with_prefilled(&kw::TABLE);
+/// assert_eq!(interner.resolve(kw::Let), Some("let"));
+///
+/// let symbol = interner.get_or_intern("struct");
+/// let is_item = match symbol {
+///     kw::Fn | kw::Struct => true,
+///     _ => false,
+/// };
+/// assert!(is_item);
+/// ```
+#[macro_export]
+macro_rules! symbols {
+    (
+        $( #[$attr:meta] )*
+        $vis:vis mod $module:ident {
+            $( $( #[$symbol_attr:meta] )* $symbol:ident: $string:literal ),* $(,)?
+        }
+    ) => {
+        $( #[$attr] )*
+        #[allow(non_upper_case_globals)]
+        $vis mod $module {
+            /// Assigns every symbol its index in declaration order.
+            #[allow(non_camel_case_types, dead_code)]
+            enum __Index {
+                $( $symbol, )*
+                __Len,
+            }
+
+            $(
+                $( #[$symbol_attr] )*
+                pub const $symbol: $crate::DefaultSymbol =
+                    $crate::DefaultSymbol::new(__Index::$symbol as usize);
+            )*
+
+            /// All strings of the declared symbols in declaration order.
+            pub const TABLE: [&'static str; __Index::__Len as usize] = [$( $string ),*];
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use core::mem::size_of;
 
+    #[test]
+    fn const_new_works() {
+        const SYMBOL: SymbolU16 = SymbolU16::new(42);
+        assert_eq!(SYMBOL.to_usize(), 42);
+        assert_eq!(SymbolU16::try_from_usize(42), Some(SYMBOL));
+        assert_eq!(SymbolU32::new(0).to_usize(), 0);
+        assert_eq!(SymbolUsize::new(7).to_usize(), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "encountered invalid symbol")]
+    fn const_new_panics_for_invalid_index() {
+        SymbolU16::new(u16::MAX as usize);
+    }
+
     #[test]
     fn same_size_as_u32() {
         assert_eq!(size_of::<DefaultSymbol>(), size_of::<u32>());
@@ -121,6 +377,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inline_symbol_works() {
+        for string in ["", "i", "id", "1234567"] {
+            let symbol = InlineSymbolU64::try_inline(string).unwrap();
+            assert_eq!(symbol.inline_str(), Some(string));
+            assert_eq!(symbol.to_usize(), usize::MAX);
+        }
+        assert_eq!(InlineSymbolU64::try_inline("12345678"), None);
+        assert_ne!(
+            InlineSymbolU64::try_inline(""),
+            InlineSymbolU64::try_inline("\0")
+        );
+        let symbol = InlineSymbolU64::try_from_usize(42).unwrap();
+        assert_eq!(symbol.to_usize(), 42);
+        assert_eq!(symbol.inline_str(), None);
+        assert_eq!(InlineSymbolU64::try_from_usize(0).unwrap().to_usize(), 0);
+        let max = (1 << 56) - 2;
+        assert_eq!(
+            InlineSymbolU64::try_from_usize(max).unwrap().to_usize(),
+            max
+        );
+        assert_eq!(InlineSymbolU64::try_from_usize(max + 1), None);
+        assert_eq!(size_of::<InlineSymbolU64>(), size_of::<u64>());
+        assert_eq!(size_of::<Option<InlineSymbolU64>>(), size_of::<u64>());
+    }
+
     #[test]
     fn try_from_usize_works() {
         assert_eq!(
@@ -16,6 +16,12 @@ pub struct TracingAllocator {
     pub stats: TracedStats,
 }
 
+impl Default for TracingAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TracingAllocator {
     pub const fn new() -> Self {
         Self {
@@ -44,8 +50,7 @@ impl TracingAllocator {
 unsafe impl GlobalAlloc for TracingAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
         self.stats.push_allocations(layout);
-        let res = self.inner.alloc(layout);
-        res
+        self.inner.alloc(layout)
     }
 
     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
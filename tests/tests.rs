@@ -3,8 +3,12 @@ mod allocator;
 use allocator::TracingAllocator;
 use string_interner::{
     backend,
+    dedup::CachedHash,
+    symbol::SymbolU16,
     DefaultHashBuilder,
     DefaultSymbol,
+    Entry,
+    InternerError,
     Symbol,
 };
 
@@ -32,30 +36,112 @@ pub trait BackendStats {
     const MAX_OVERHEAD: f64;
     /// The name of the backend for debug display purpose.
     const NAME: &'static str;
+    /// The same backend using 16-bit symbols.
+    type WithSymbolU16: backend::Backend<SymbolU16>;
 }
 
 impl BackendStats for backend::BucketBackend<DefaultSymbol> {
     const MIN_OVERHEAD: f64 = 2.45;
     const MAX_OVERHEAD: f64 = 3.25;
     const NAME: &'static str = "BucketBackend";
+    type WithSymbolU16 = backend::BucketBackend<SymbolU16>;
 }
 
 impl BackendStats for backend::SimpleBackend<DefaultSymbol> {
     const MIN_OVERHEAD: f64 = 2.25;
     const MAX_OVERHEAD: f64 = 2.85;
     const NAME: &'static str = "SimpleBackend";
+    type WithSymbolU16 = backend::SimpleBackend<SymbolU16>;
+}
+
+impl BackendStats for backend::ArcBackend<DefaultSymbol> {
+    const MIN_OVERHEAD: f64 = 3.25;
+    const MAX_OVERHEAD: f64 = 3.90;
+    const NAME: &'static str = "ArcBackend";
+    type WithSymbolU16 = backend::ArcBackend<SymbolU16>;
+}
+
+impl BackendStats for backend::BufferBackend<DefaultSymbol> {
+    const MIN_OVERHEAD: f64 = 1.55;
+    const MAX_OVERHEAD: f64 = 2.30;
+    const NAME: &'static str = "BufferBackend";
+    type WithSymbolU16 = backend::BufferBackend<SymbolU16>;
 }
 
 impl BackendStats for backend::StringBackend<DefaultSymbol> {
     const MIN_OVERHEAD: f64 = 1.70;
     const MAX_OVERHEAD: f64 = 2.55;
     const NAME: &'static str = "StringBackend";
+    type WithSymbolU16 = backend::StringBackend<SymbolU16>;
 }
 
-macro_rules! gen_tests_for_backend {
+impl BackendStats for backend::StringBackend<DefaultSymbol, str, u64> {
+    const MIN_OVERHEAD: f64 = 1.95;
+    const MAX_OVERHEAD: f64 = 2.90;
+    const NAME: &'static str = "StringBackend<u64>";
+    type WithSymbolU16 = backend::StringBackend<SymbolU16, str, u64>;
+}
+
+impl BackendStats for backend::ValueBackend<DefaultSymbol, str> {
+    const MIN_OVERHEAD: f64 = 2.75;
+    const MAX_OVERHEAD: f64 = 3.60;
+    const NAME: &'static str = "ValueBackend";
+    type WithSymbolU16 = backend::ValueBackend<SymbolU16, str>;
+}
+
+impl BackendStats for backend::FreeListBackend<DefaultSymbol> {
+    const MIN_OVERHEAD: f64 = 2.75;
+    const MAX_OVERHEAD: f64 = 3.60;
+    const NAME: &'static str = "FreeListBackend";
+    type WithSymbolU16 = backend::FreeListBackend<SymbolU16>;
+}
+
+impl BackendStats for backend::LeakingBackend<DefaultSymbol> {
+    const MIN_OVERHEAD: f64 = 2.30;
+    const MAX_OVERHEAD: f64 = 2.90;
+    const NAME: &'static str = "LeakingBackend";
+    type WithSymbolU16 = backend::LeakingBackend<SymbolU16>;
+}
+
+/// Stats for the keys of the deduplication table.
+pub trait DedupStats {
+    /// The expected minimum memory overhead on top of the backend's.
+    const EXTRA_MIN_OVERHEAD: f64;
+    /// The expected maximum memory overhead on top of the backend's.
+    const EXTRA_MAX_OVERHEAD: f64;
+    /// The name of the key for debug display purpose.
+    const NAME: &'static str;
+}
+
+impl DedupStats for DefaultSymbol {
+    const EXTRA_MIN_OVERHEAD: f64 = 0.0;
+    const EXTRA_MAX_OVERHEAD: f64 = 0.0;
+    const NAME: &'static str = "DefaultSymbol";
+}
+
+impl DedupStats for CachedHash<DefaultSymbol> {
+    const EXTRA_MIN_OVERHEAD: f64 = 0.80;
+    const EXTRA_MAX_OVERHEAD: f64 = 1.10;
+    const NAME: &'static str = "CachedHash";
+}
+
+string_interner::symbols! {
+    /// Keywords used to test prefilled interners.
+    mod kw {
+        Fn: "fn",
+        Let: "let",
+        /// Attributes are forwarded to the symbols.
+        Struct: "struct",
+    }
+}
+
+macro_rules! gen_memory_test_for_backend {
     ( $backend:ty ) => {
+        gen_memory_test_for_backend!($backend, DefaultSymbol);
+    };
+    ( $backend:ty, $key:ty ) => {
         type StringInterner =
-            string_interner::StringInterner<DefaultSymbol, $backend, DefaultHashBuilder>;
+            string_interner::StringInterner<DefaultSymbol, $backend, DefaultHashBuilder, $key>;
 
         fn profile_memory_usage(words: &[String]) -> f64 {
             ALLOCATOR.reset();
@@ -108,7 +194,11 @@ macro_rules! gen_tests_for_backend {
             }).collect::<Vec<_>>();
 
             println!();
-            println!("Benchmark Memory Usage for {}", <$backend as BackendStats>::NAME);
+            println!(
+                "Benchmark Memory Usage for {} with {} keys",
+                <$backend as BackendStats>::NAME,
+                <$key as DedupStats>::NAME,
+            );
             let mut min_overhead = None;
             let mut max_overhead = None;
             for i in 0..10 {
@@ -124,8 +214,10 @@ macro_rules! gen_tests_for_backend {
             }
             let actual_min_overhead = min_overhead.unwrap();
             let actual_max_overhead = max_overhead.unwrap();
-            let expect_min_overhead = <$backend as BackendStats>::MIN_OVERHEAD;
-            let expect_max_overhead = <$backend as BackendStats>::MAX_OVERHEAD;
+            let expect_min_overhead =
+                <$backend as BackendStats>::MIN_OVERHEAD + <$key as DedupStats>::EXTRA_MIN_OVERHEAD;
+            let expect_max_overhead =
+                <$backend as BackendStats>::MAX_OVERHEAD + <$key as DedupStats>::EXTRA_MAX_OVERHEAD;
 
             println!();
             println!("- % min. overhead = {:.02}%", actual_min_overhead * 100.0);
@@ -146,6 +238,15 @@ macro_rules! gen_tests_for_backend {
                 actual_max_overhead,
             );
         }
+    };
+}
+
+macro_rules! gen_tests_for_backend {
+    ( $backend:ty ) => {
+        gen_tests_for_backend!($backend, DefaultSymbol);
+    };
+    ( $backend:ty, $key:ty ) => {
+        gen_memory_test_for_backend!($backend, $key);
 
         #[test]
         fn new_works() {
@@ -164,6 +265,256 @@ macro_rules! gen_tests_for_backend {
             assert!(!interner.is_empty());
         }
 
+        #[test]
+        fn clear_works() {
+            let mut interner = StringInterner::new();
+            interner.extend(&["a", "b", "c"]);
+            interner.clear();
+            assert!(interner.is_empty());
+            assert_eq!(interner.get("a"), None);
+            let d = interner.get_or_intern("d");
+            let a = interner.get_or_intern("a");
+            assert_eq!(interner.len(), 2);
+            assert_eq!(interner.resolve(d), Some("d"));
+            assert_eq!(interner.resolve(a), Some("a"));
+            assert_eq!(interner.get("a"), Some(a));
+        }
+
+        #[test]
+        fn reserve_works() {
+            let mut interner = StringInterner::new();
+            interner.get_or_intern("a");
+            interner.reserve(100, 1_000);
+            assert!(interner.capacity() >= 101);
+            let symbols = (0..100)
+                .map(|i| interner.get_or_intern(i.to_string()))
+                .collect::<Vec<_>>();
+            assert_eq!(interner.len(), 101);
+            for (i, symbol) in symbols.into_iter().enumerate() {
+                assert_eq!(interner.resolve(symbol), Some(i.to_string().as_str()));
+                assert_eq!(interner.get(i.to_string()), Some(symbol));
+            }
+        }
+
+        #[test]
+        fn shrink_to_fit_works() {
+            let mut interner = StringInterner::new();
+            let symbols = ["a", "b", "c"].map(|s| interner.get_or_intern(s));
+            interner.reserve(1_000, 10_000);
+            interner.shrink_to_fit();
+            assert!(interner.capacity() >= 3);
+            assert!(interner.capacity() < 1_000);
+            for (&symbol, &string) in symbols.iter().zip(&["a", "b", "c"]) {
+                assert_eq!(interner.resolve(symbol), Some(string));
+                assert_eq!(interner.get(string), Some(symbol));
+            }
+        }
+
+        #[test]
+        fn memory_stats_works() {
+            let mut interner = StringInterner::new();
+            let stats = interner.memory_stats();
+            assert_eq!(stats.dedup_len, 0);
+            assert_eq!(stats.dedup_load_factor(), 0.0);
+            interner.extend(&["a", "bb", "ccc"]);
+            let stats = interner.memory_stats();
+            assert_eq!(stats.dedup_len, 3);
+            assert!(stats.dedup_capacity >= 3);
+            assert!(stats.dedup_bytes > 0);
+            assert!(stats.dedup_load_factor() > 0.0 && stats.dedup_load_factor() <= 1.0);
+            assert_eq!(stats.backend.index_len, 3);
+            assert!(stats.backend.index_capacity >= 3);
+            assert!(stats.backend.string_bytes > 0);
+            assert_eq!(interner.heap_size(), stats.heap_size());
+            assert!(interner.heap_size() > stats.dedup_bytes);
+        }
+
+        #[test]
+        fn with_prefilled_works() {
+            let mut interner = StringInterner::with_prefilled(&kw::TABLE);
+            assert_eq!(interner.len(), 3);
+            assert_eq!(interner.resolve(kw::Fn), Some("fn"));
+            assert_eq!(interner.resolve(kw::Let), Some("let"));
+            assert_eq!(interner.resolve(kw::Struct), Some("struct"));
+            assert_eq!(interner.get_or_intern("let"), kw::Let);
+            assert_eq!(interner.get_or_intern("enum").to_usize(), 3);
+        }
+
+        #[test]
+        #[should_panic(expected = "duplicate")]
+        fn with_prefilled_rejects_duplicates() {
+            StringInterner::with_prefilled(&["a", "b", "a"]);
+        }
+
+        #[test]
+        fn resolver_works() {
+            use string_interner::Resolver;
+
+            fn check(resolver: &dyn Resolver<DefaultSymbol>, symbols: &[DefaultSymbol]) {
+                assert_eq!(resolver.len(), 3);
+                assert!(!resolver.is_empty());
+                assert_eq!(resolver.resolve(symbols[1]), Some("b"));
+                // SAFETY: The symbol has been handed out for the resolver.
+                assert_eq!(unsafe { resolver.resolve_unchecked(symbols[2]) }, "c");
+                let expected = symbols.iter().copied().zip(vec!["a", "b", "c"]);
+                assert_eq!(resolver.iter().collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+            }
+
+            let mut interner = StringInterner::new();
+            let symbols = interner.get_or_intern_many(&["a", "b", "c"]);
+            check(&interner, &symbols);
+            let frozen = interner.freeze();
+            check(&frozen, &symbols);
+            check(&frozen.into_resolver(), &symbols);
+            let mut backend = <$backend>::default();
+            let symbols = ["a", "b", "c"]
+                .iter()
+                .map(|string| backend::Backend::intern(&mut backend, string))
+                .collect::<Vec<_>>();
+            check(&backend, &symbols);
+        }
+
+        #[test]
+        fn merge_from_works() {
+            let mut local = StringInterner::new();
+            let symbols = local.get_or_intern_many(&["a", "b", "c"]);
+            let mut global = StringInterner::new();
+            let d = global.get_or_intern("d");
+            let b = global.get_or_intern("b");
+            let remap = global.merge_from(&local);
+            assert_eq!(remap.len(), 3);
+            assert!(!remap.is_identity());
+            assert_eq!(remap.apply(symbols[1]), b);
+            assert_eq!(global.len(), 4);
+            assert_eq!(global.get("d"), Some(d));
+            for (from, to) in &remap {
+                assert_eq!(global.resolve(to), local.resolve(from));
+            }
+            let mut mapped = symbols.clone();
+            remap.apply_in_place(&mut mapped);
+            assert_eq!(mapped, symbols.iter().map(|&symbol| remap.apply(symbol)).collect::<Vec<_>>());
+
+            let mut empty = StringInterner::new();
+            let remap = empty.merge_from(&local);
+            assert!(remap.is_identity());
+            assert_eq!(empty.len(), 3);
+            assert_eq!(empty.get("c"), Some(remap.apply(symbols[2])));
+            assert_eq!(empty.get_or_intern("a"), remap.apply(symbols[0]));
+            assert_eq!(empty.len(), 3);
+        }
+
+        #[test]
+        fn merge_from_same_backend_works() {
+            let mut local = StringInterner::new();
+            let symbols = local.get_or_intern_many(&["a", "bb", "", "ccc"]);
+            let mut empty = StringInterner::new();
+            let remap = empty.merge_from_same_backend(&local);
+            assert_eq!(remap.len(), 4);
+            assert!(remap.is_identity());
+            assert_eq!(empty, local);
+            for (&symbol, string) in symbols.iter().zip(&["a", "bb", "", "ccc"]) {
+                assert_eq!(empty.get(string), Some(remap.apply(symbol)));
+                assert_eq!(empty.resolve(remap.apply(symbol)), Some(*string));
+            }
+            let d = empty.get_or_intern("d");
+            assert_eq!(empty.get_or_intern("bb"), remap.apply(symbols[1]));
+            assert_eq!(empty.len(), 5);
+
+            let mut global = StringInterner::new();
+            let e = global.get_or_intern("e");
+            let bb = global.get_or_intern("bb");
+            let remap = global.merge_from_same_backend(&empty);
+            assert_eq!(remap.apply(symbols[1]), bb);
+            assert_eq!(global.get("e"), Some(e));
+            assert_eq!(global.resolve(remap.apply(d)), Some("d"));
+            assert_eq!(global.len(), 6);
+        }
+
+        #[test]
+        fn entry_works() {
+            let mut interner = StringInterner::new();
+            let foo = match interner.entry("foo") {
+                Entry::Occupied(_) => panic!("unexpected occupied entry"),
+                Entry::Vacant(vacant) => {
+                    assert_eq!(vacant.string(), "foo");
+                    vacant.insert()
+                }
+            };
+            let bar = match interner.entry("bar") {
+                Entry::Occupied(_) => panic!("unexpected occupied entry"),
+                Entry::Vacant(vacant) => vacant.insert_static(),
+            };
+            assert_ne!(foo, bar);
+            assert!(matches!(interner.entry("foo"), Entry::Occupied(symbol) if symbol == foo));
+            assert_eq!(interner.entry("bar").or_insert(), bar);
+            assert_eq!(interner.get("foo"), Some(foo));
+            assert_eq!(interner.resolve(bar), Some("bar"));
+            assert_eq!(interner.len(), 2);
+        }
+
+        #[test]
+        fn get_or_intern_inserted_works() {
+            let mut interner = StringInterner::new();
+            let (foo, inserted) = interner.get_or_intern_inserted("foo");
+            assert!(inserted);
+            assert_eq!(interner.get_or_intern_inserted("foo"), (foo, false));
+            let (bar, inserted) = interner.get_or_intern_inserted(String::from("bar"));
+            assert!(inserted);
+            assert_ne!(foo, bar);
+            assert_eq!(interner.len(), 2);
+        }
+
+        #[test]
+        fn with_hash_works() {
+            let mut interner = StringInterner::new();
+            let hash = interner.hash_of("foo");
+            assert_eq!(interner.get_with_hash(hash, "foo"), None);
+            let foo = interner.get_or_intern_with_hash(hash, "foo");
+            assert_eq!(interner.get_or_intern_with_hash(hash, "foo"), foo);
+            assert_eq!(interner.get_with_hash(hash, "foo"), Some(foo));
+            assert_eq!(interner.get_or_intern("foo"), foo);
+            assert_eq!(interner.resolve(foo), Some("foo"));
+            assert_eq!(interner.len(), 1);
+        }
+
+        #[test]
+        #[cfg(debug_assertions)]
+        #[should_panic(expected = "inconsistent")]
+        fn with_hash_rejects_inconsistent_hash() {
+            let mut interner = StringInterner::new();
+            let hash = interner.hash_of("foo");
+            interner.get_or_intern_with_hash(hash, "bar");
+        }
+
+        #[test]
+        fn get_or_intern_many_works() {
+            let mut interner = StringInterner::new();
+            let foo = interner.get_or_intern("foo");
+            let symbols = interner.get_or_intern_many(&["bar", "foo", "baz", "bar"]);
+            assert_eq!(symbols.len(), 4);
+            assert_eq!(symbols[1], foo);
+            assert_eq!(symbols[0], symbols[3]);
+            assert_ne!(symbols[0], symbols[2]);
+            assert_eq!(interner.len(), 3);
+            for (symbol, string) in symbols.iter().zip(&["bar", "foo", "baz", "bar"]) {
+                assert_eq!(interner.resolve(*symbol), Some(*string));
+            }
+            let symbols = interner.get_or_intern_many(Vec::<String>::new());
+            assert!(symbols.is_empty());
+        }
+
+        #[test]
+        fn get_or_intern_many_streams_chunks() {
+            let mut interner = StringInterner::new();
+            let strings = (0..100).map(|i| (i % 70).to_string());
+            let symbols = interner.get_or_intern_many(strings.filter(|_| true));
+            assert_eq!(symbols.len(), 100);
+            assert_eq!(interner.len(), 70);
+            for (i, symbol) in symbols.iter().enumerate() {
+                assert_eq!(interner.resolve(*symbol), Some(&*(i % 70).to_string()));
+            }
+        }
+
         #[test]
         fn clone_works() {
             let mut interner = StringInterner::new();
@@ -205,6 +556,45 @@ macro_rules! gen_tests_for_backend {
             assert_eq!(interner.len(), 3);
         }
 
+        #[test]
+        fn try_get_or_intern_works() {
+            let mut interner = StringInterner::new();
+            // Insert 3 unique strings:
+            assert_eq!(interner.try_get_or_intern("a").map(Symbol::to_usize), Ok(0));
+            assert_eq!(interner.try_get_or_intern_static("b").map(Symbol::to_usize), Ok(1));
+            assert_eq!(interner.try_get_or_intern("c").map(Symbol::to_usize), Ok(2));
+            assert_eq!(interner.len(), 3);
+            // Insert the same 3 unique strings, yield the same symbols:
+            assert_eq!(interner.try_get_or_intern_static("a").map(Symbol::to_usize), Ok(0));
+            assert_eq!(interner.try_get_or_intern("b").map(Symbol::to_usize), Ok(1));
+            assert_eq!(interner.get_or_intern("c").to_usize(), 2);
+            assert_eq!(interner.len(), 3);
+        }
+
+        #[test]
+        fn try_get_or_intern_exhausts_symbols() {
+            let mut interner = string_interner::StringInterner::<
+                SymbolU16,
+                <$backend as BackendStats>::WithSymbolU16,
+                DefaultHashBuilder,
+            >::new();
+            let len_symbols = u16::MAX as usize;
+            for i in 0..len_symbols {
+                assert_eq!(interner.try_get_or_intern(i.to_string()).map(Symbol::to_usize), Ok(i));
+            }
+            assert_eq!(
+                interner.try_get_or_intern(len_symbols.to_string()),
+                Err(InternerError::SymbolsExhausted),
+            );
+            assert_eq!(
+                interner.try_get_or_intern_static("static"),
+                Err(InternerError::SymbolsExhausted),
+            );
+            // Already interned strings still yield their symbols:
+            assert_eq!(interner.try_get_or_intern("0").map(Symbol::to_usize), Ok(0));
+            assert_eq!(interner.len(), len_symbols);
+        }
+
         #[test]
         fn resolve_works() {
             let mut interner = StringInterner::new();
@@ -286,6 +676,51 @@ mod bucket_backend {
     use super::*;
 
     gen_tests_for_backend!(backend::BucketBackend<DefaultSymbol>);
+
+    #[test]
+    fn memory_stats_report_slack() {
+        let mut interner = string_interner::StringInterner::<
+            DefaultSymbol,
+            backend::BucketBackend<DefaultSymbol>,
+            DefaultHashBuilder,
+        >::new();
+        interner.extend(&["a", "bb", "ccc"]);
+        interner.get_or_intern_static("static");
+        let stats = interner.memory_stats().backend;
+        assert_eq!(stats.string_bytes, 6);
+        assert!(stats.slack_bytes > 0);
+        assert_eq!(stats.index_len, 4);
+    }
+
+    #[test]
+    fn clear_keeps_buckets_for_refill() {
+        let mut interner = string_interner::StringInterner::<
+            DefaultSymbol,
+            backend::BucketBackend<DefaultSymbol>,
+            DefaultHashBuilder,
+        >::new();
+        let strings = (0..1000).map(|i| i.to_string()).collect::<Vec<_>>();
+        interner.extend(&strings);
+        let capacity = interner.capacity();
+        let stats = interner.memory_stats().backend;
+        interner.clear();
+        interner.extend(&strings);
+        assert_eq!(interner.capacity(), capacity);
+        let refilled = interner.memory_stats().backend;
+        assert_eq!(refilled.string_bytes, stats.string_bytes);
+        assert_eq!(refilled.slack_bytes, stats.slack_bytes);
+        // Once the free list itself is allocated, refills no longer allocate.
+        let heap_size = interner.memory_stats().heap_size();
+        interner.clear();
+        assert_eq!(interner.memory_stats().heap_size(), heap_size);
+        interner.extend(&strings);
+        assert_eq!(interner.capacity(), capacity);
+        assert_eq!(interner.memory_stats().heap_size(), heap_size);
+        for (index, string) in strings.iter().enumerate() {
+            let symbol = DefaultSymbol::try_from_usize(index).unwrap();
+            assert_eq!(interner.resolve(symbol), Some(string.as_str()));
+        }
+    }
 }
 
 mod simple_backend {
@@ -294,8 +729,1156 @@ mod simple_backend {
     gen_tests_for_backend!(backend::SimpleBackend<DefaultSymbol>);
 }
 
+mod string_backend_cached_hash {
+    use super::*;
+
+    gen_tests_for_backend!(
+        backend::StringBackend<DefaultSymbol>,
+        CachedHash<DefaultSymbol>
+    );
+}
+
+mod bucket_backend_cached_hash {
+    use super::*;
+
+    gen_tests_for_backend!(
+        backend::BucketBackend<DefaultSymbol>,
+        CachedHash<DefaultSymbol>
+    );
+}
+
+mod arc_backend {
+    use super::*;
+    use std::sync::Arc;
+
+    gen_tests_for_backend!(backend::ArcBackend<DefaultSymbol>);
+
+    #[test]
+    fn resolve_arc_works() {
+        let mut interner = StringInterner::new();
+        let foo = interner.get_or_intern("foo");
+        let resolved = interner.resolve_arc(foo).unwrap();
+        assert_eq!(&*resolved, "foo");
+        assert!(Arc::ptr_eq(&resolved, &interner.resolve_arc(foo).unwrap()));
+        drop(interner);
+        assert_eq!(&*resolved, "foo");
+    }
+
+    #[test]
+    fn get_or_intern_arc_works() {
+        let mut interner = StringInterner::new();
+        let foo = Arc::<str>::from("foo");
+        let symbol = interner.get_or_intern_arc(Arc::clone(&foo));
+        assert!(Arc::ptr_eq(&interner.resolve_arc(symbol).unwrap(), &foo));
+        assert_eq!(interner.get_or_intern("foo"), symbol);
+        let bar = interner.get_or_intern("bar");
+        let other = Arc::<str>::from("bar");
+        assert_eq!(interner.get_or_intern_arc(Arc::clone(&other)), bar);
+        assert!(!Arc::ptr_eq(&interner.resolve_arc(bar).unwrap(), &other));
+        assert_eq!(Arc::strong_count(&other), 1);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn resolve_arc_works_for_inline_symbols() {
+        let mut interner = string_interner::Interner::<
+            str,
+            string_interner::symbol::InlineSymbolU64,
+            backend::ArcBackend<string_interner::symbol::InlineSymbolU64>,
+        >::new();
+        let x = interner.get_or_intern_arc(Arc::from("x"));
+        assert!(interner.is_empty());
+        assert_eq!(interner.resolve_arc(x).as_deref(), Some("x"));
+    }
+}
+
 mod string_backend {
     use super::*;
 
     gen_tests_for_backend!(backend::StringBackend<DefaultSymbol>);
+
+    #[test]
+    fn extend_from_shifts_symbols() {
+        use string_interner::backend::Backend as _;
+        let mut lhs = <backend::StringBackend<DefaultSymbol>>::default();
+        lhs.intern("a");
+        let mut rhs = <backend::StringBackend<DefaultSymbol>>::default();
+        let bb = rhs.intern("bb");
+        let c = rhs.intern("c");
+        assert_eq!(lhs.extend_from(&rhs), Some(1));
+        assert_eq!(lhs.resolve(expect_valid_symbol(0)), Some("a"));
+        assert_eq!(
+            lhs.resolve(expect_valid_symbol(bb.to_usize() + 1)),
+            Some("bb")
+        );
+        assert_eq!(
+            lhs.resolve(expect_valid_symbol(c.to_usize() + 1)),
+            Some("c")
+        );
+        assert_eq!(lhs.intern("d").to_usize(), 3);
+
+        let mut lhs = <backend::StringBackend<DefaultSymbol, str, u16>>::default();
+        lhs.intern(&"x".repeat(usize::from(u16::MAX)));
+        let mut rhs = <backend::StringBackend<DefaultSymbol, str, u16>>::default();
+        rhs.intern("y");
+        assert_eq!(lhs.extend_from(&rhs), None);
+        assert_eq!(lhs.memory_stats().string_bytes, usize::from(u16::MAX));
+    }
+
+    type OffsetInterner<O> = string_interner::StringInterner<
+        DefaultSymbol,
+        backend::StringBackend<DefaultSymbol, str, O>,
+        DefaultHashBuilder,
+    >;
+
+    #[test]
+    fn offset_overflow_works() {
+        let chunk = "x".repeat(1000);
+        let mut narrow = OffsetInterner::<u16>::new();
+        let mut wide = OffsetInterner::<u32>::new();
+        for i in 0..65 {
+            let string = format!("{}{}", i, chunk);
+            narrow.get_or_intern(&string);
+            wide.get_or_intern(&string);
+        }
+        assert_eq!(
+            narrow.try_get_or_intern(format!("65{}", chunk)),
+            Err(InternerError::StorageOverflow)
+        );
+        assert_eq!(narrow.len(), 65);
+        let last = narrow.get(format!("64{}", chunk)).unwrap();
+        assert_eq!(narrow.resolve(last).map(str::len), Some(1002));
+        let symbol = wide.get_or_intern(format!("65{}", chunk));
+        assert_eq!(wide.resolve(symbol), Some(format!("65{}", chunk).as_str()));
+    }
+
+    #[test]
+    #[should_panic(expected = "wider offset type")]
+    fn offset_overflow_panics() {
+        let mut interner = OffsetInterner::<u16>::new();
+        interner.get_or_intern("x".repeat(usize::from(u16::MAX) + 1));
+    }
+}
+
+mod string_backend_u64 {
+    use super::*;
+
+    gen_tests_for_backend!(backend::StringBackend<DefaultSymbol, str, u64>);
+}
+
+mod buffer_backend {
+    use super::*;
+    use string_interner::Resolver;
+
+    gen_memory_test_for_backend!(backend::BufferBackend<DefaultSymbol>);
+
+    #[test]
+    fn get_or_intern_works() {
+        let mut interner = StringInterner::new();
+        let long = "x".repeat(300);
+        let strings = ["aa", "", "bbb", long.as_str(), "ünïcödé"];
+        let symbols = interner.get_or_intern_many(&strings);
+        assert_eq!(interner.len(), strings.len());
+        assert_eq!(interner.get_or_intern("bbb"), symbols[2]);
+        assert_eq!(interner.len(), strings.len());
+        for (&symbol, &string) in symbols.iter().zip(&strings) {
+            assert_eq!(interner.resolve(symbol), Some(string));
+            assert_eq!(interner.get(string), Some(symbol));
+        }
+        assert_eq!(
+            interner.into_iter().collect::<Vec<_>>(),
+            symbols
+                .iter()
+                .copied()
+                .zip(strings.iter().copied())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn footprint_beats_string_backend_for_short_strings() {
+        use string_interner::backend::Backend as _;
+        let strings = (0..10_000).map(|i| format!("{:x}", i)).collect::<Vec<_>>();
+        let mut buffer = <backend::BufferBackend<DefaultSymbol>>::default();
+        let mut string = <backend::StringBackend<DefaultSymbol>>::default();
+        for s in &strings {
+            buffer.intern(s);
+            string.intern(s);
+        }
+        buffer.shrink_to_fit();
+        string.shrink_to_fit();
+        let buffer = buffer.memory_stats();
+        let string = string.memory_stats();
+        assert_eq!(buffer.string_bytes, string.string_bytes);
+        // One prefix byte per string instead of one `u32` end offset.
+        assert_eq!(buffer.heap_size(), string.string_bytes + strings.len());
+        assert_eq!(
+            string.heap_size(),
+            string.string_bytes + 4 * (strings.len() + 1)
+        );
+    }
+
+    #[test]
+    fn resolve_validates_strings_at_foreign_offsets() {
+        // The string bytes are `[1, 0xC3, 0xA9]` so that the offset of its
+        // second byte decodes as a length prefix of `1` followed by `0xC3`.
+        let mut interner = StringInterner::new();
+        interner.get_or_intern("\u{1}é");
+        assert_eq!(interner.resolve(expect_valid_symbol(1)), None);
+        let mut interner = string_interner::BytesInterner::<
+            DefaultSymbol,
+            backend::BufferBackend<DefaultSymbol, [u8]>,
+            DefaultHashBuilder,
+        >::new();
+        interner.get_or_intern(&b"\x01\xC3\xA9"[..]);
+        assert_eq!(interner.resolve(expect_valid_symbol(1)), Some(&b"\xC3"[..]));
+    }
+
+    #[test]
+    fn extend_from_shifts_symbols() {
+        use string_interner::backend::Backend;
+        let mut lhs = <backend::BufferBackend<DefaultSymbol>>::default();
+        lhs.intern("a");
+        let mut rhs = <backend::BufferBackend<DefaultSymbol>>::default();
+        let bb = rhs.intern("bb");
+        let c = rhs.intern("c");
+        assert_eq!(lhs.extend_from(&rhs), Some(2));
+        assert_eq!(Backend::resolve(&lhs, expect_valid_symbol(0)), Some("a"));
+        let bb = expect_valid_symbol(bb.to_usize() + 2);
+        assert_eq!(Backend::resolve(&lhs, bb), Some("bb"));
+        let c = expect_valid_symbol(c.to_usize() + 2);
+        assert_eq!(Backend::resolve(&lhs, c), Some("c"));
+        assert_eq!(Resolver::len(&lhs), 3);
+        assert_eq!(lhs.memory_stats().string_bytes, 4);
+    }
+
+    #[test]
+    fn symbols_are_offsets() {
+        let mut interner = StringInterner::new();
+        assert_eq!(interner.get_or_intern("aa").to_usize(), 0);
+        assert_eq!(interner.get_or_intern("b").to_usize(), 3);
+        assert_eq!(interner.get_or_intern("c").to_usize(), 5);
+    }
+
+    #[test]
+    fn resolve_rejects_foreign_symbols() {
+        let mut interner = StringInterner::new();
+        interner.get_or_intern("ünïcödé");
+        for offset in 1..16 {
+            assert_eq!(interner.resolve(expect_valid_symbol(offset)), None);
+        }
+    }
+
+    #[test]
+    fn clear_works() {
+        let mut interner = StringInterner::new();
+        interner.extend(&["a", "b", "c"]);
+        interner.clear();
+        assert!(interner.is_empty());
+        assert_eq!(interner.get("a"), None);
+        let d = interner.get_or_intern("d");
+        assert_eq!(d.to_usize(), 0);
+        assert_eq!(interner.resolve(d), Some("d"));
+    }
+
+    #[test]
+    fn symbols_exhausted_works() {
+        let mut interner = string_interner::StringInterner::<
+            SymbolU16,
+            backend::BufferBackend<SymbolU16>,
+            DefaultHashBuilder,
+        >::new();
+        let chunk = "x".repeat(1000);
+        let mut i = 0;
+        let error = loop {
+            match interner.try_get_or_intern(format!("{}{}", i, chunk)) {
+                Ok(_) => i += 1,
+                Err(error) => break error,
+            }
+        };
+        assert_eq!(error, InternerError::SymbolsExhausted);
+        assert_eq!(interner.len(), i);
+    }
+
+    #[test]
+    fn bytes_work() {
+        let mut interner = string_interner::BytesInterner::<
+            DefaultSymbol,
+            backend::BufferBackend<DefaultSymbol, [u8]>,
+            DefaultHashBuilder,
+        >::new();
+        let a = interner.get_or_intern(b"a");
+        let b = interner.get_or_intern(&[0xFF, 0xFE][..]);
+        assert_eq!(interner.get_or_intern(vec![0xFF, 0xFE]), b);
+        assert_eq!(interner.resolve(a), Some(&b"a"[..]));
+        assert_eq!(interner.resolve(b), Some(&[0xFF, 0xFE][..]));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn backend_works() {
+        let mut backend = backend::BufferBackend::<DefaultSymbol>::default();
+        let a = backend::Backend::intern(&mut backend, "a");
+        let b = backend::Backend::intern(&mut backend, "bb");
+        assert_eq!(Resolver::len(&backend), 2);
+        assert_eq!(Resolver::resolve(&backend, a), Some("a"));
+        assert_eq!(Resolver::resolve(&backend, b), Some("bb"));
+        assert_eq!((&backend).into_iter().count(), 2);
+        let stats = backend::Backend::memory_stats(&backend);
+        assert_eq!(stats.string_bytes, 3);
+        assert_eq!(stats.index_len, 2);
+    }
+}
+
+mod free_list_backend {
+    use super::*;
+
+    gen_tests_for_backend!(backend::FreeListBackend<DefaultSymbol>);
+
+    type FreeListInterner<S> = string_interner::StringInterner<
+        S,
+        backend::FreeListBackend<S>,
+        DefaultHashBuilder,
+    >;
+
+    #[test]
+    fn remove_works() {
+        let mut interner = FreeListInterner::<DefaultSymbol>::new();
+        let aa = interner.get_or_intern("aa");
+        let bb = interner.get_or_intern("bb");
+        assert_eq!(interner.len(), 2);
+        assert!(interner.remove(aa));
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.resolve(aa), None);
+        assert_eq!(interner.get("aa"), None);
+        assert_eq!(interner.resolve(bb), Some("bb"));
+        assert_eq!(interner.get("bb"), Some(bb));
+        assert!(!interner.remove(aa));
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.memory_stats().backend.string_bytes, 2);
+    }
+
+    #[test]
+    fn removed_slots_are_reused() {
+        let mut interner = FreeListInterner::<DefaultSymbol>::new();
+        let aa = interner.get_or_intern("aa");
+        let bb = interner.get_or_intern("bb");
+        assert!(interner.remove(aa));
+        let cc = interner.get_or_intern("cc");
+        // The slot of `aa` is reused with a new generation.
+        assert_eq!(cc.to_usize() & 0xFF_FFFF, aa.to_usize());
+        assert_ne!(cc, aa);
+        assert_eq!(interner.resolve(aa), None);
+        assert_eq!(interner.resolve(cc), Some("cc"));
+        assert_eq!(
+            interner.into_iter().collect::<Vec<_>>(),
+            vec![(cc, "cc"), (bb, "bb")]
+        );
+        let aa2 = interner.get_or_intern("aa");
+        assert_ne!(aa2, aa);
+        assert_eq!(interner.resolve(aa2), Some("aa"));
+    }
+
+    #[test]
+    fn merge_from_works_after_remove() {
+        let mut source = FreeListInterner::<DefaultSymbol>::new();
+        let aa = source.get_or_intern("aa");
+        let bb = source.get_or_intern("bb");
+        assert!(source.remove(aa));
+        // Reuses the slot of `aa` with a symbol that has its generation in the high bits.
+        let cc = source.get_or_intern("cc");
+        assert_eq!(cc.to_usize(), 1 << 24);
+        let mut interner = string_interner::StringInterner::default();
+        let remap = interner.merge_from(&source);
+        assert_eq!(remap.len(), 2);
+        assert_eq!(remap.get(aa), None);
+        assert_eq!(interner.resolve(remap.apply(bb)), Some("bb"));
+        assert_eq!(interner.resolve(remap.apply(cc)), Some("cc"));
+        let mut pairs = remap.iter().collect::<Vec<_>>();
+        pairs.sort_by_key(|&(from, _)| from.to_usize());
+        assert_eq!(pairs, [(bb, remap.apply(bb)), (cc, remap.apply(cc))]);
+    }
+
+    #[test]
+    fn exhausted_generations_retire_slots() {
+        let mut interner = FreeListInterner::<DefaultSymbol>::new();
+        let mut stale = Vec::new();
+        for _ in 0..=u8::MAX {
+            let symbol = interner.get_or_intern("aa");
+            assert_eq!(symbol.to_usize() & 0xFF_FFFF, 0);
+            assert!(interner.remove(symbol));
+            stale.push(symbol);
+        }
+        let symbol = interner.get_or_intern("aa");
+        assert_eq!(symbol.to_usize(), 1);
+        for symbol in stale {
+            assert_eq!(interner.resolve(symbol), None);
+        }
+    }
+
+    #[test]
+    fn symbol_u16_does_not_reuse_slots() {
+        let mut interner = FreeListInterner::<SymbolU16>::new();
+        let aa = interner.get_or_intern("aa");
+        assert!(interner.remove(aa));
+        let bb = interner.get_or_intern("bb");
+        assert_eq!(bb.to_usize(), 1);
+        assert_eq!(interner.resolve(aa), None);
+        assert_eq!(interner.resolve(bb), Some("bb"));
+    }
+}
+
+mod leaking_backend {
+    use super::*;
+    use string_interner::backend::Backend;
+
+    gen_tests_for_backend!(backend::LeakingBackend<DefaultSymbol>);
+
+    #[test]
+    fn resolve_static_works() {
+        let mut backend = backend::LeakingBackend::<DefaultSymbol>::default();
+        let foo = backend.intern("foo");
+        let bar = backend.intern_static("bar");
+        let (foo, bar) = {
+            let backend = backend.clone();
+            (backend.resolve_static(foo), backend.resolve_static(bar))
+        };
+        assert_eq!(foo, Some("foo"));
+        assert_eq!(bar, Some("bar"));
+    }
+}
+
+#[cfg(feature = "global")]
+mod global {
+    use string_interner::{
+        global,
+        intern,
+        GlobalSymbol,
+    };
+
+    #[test]
+    fn intern_works() {
+        let foo = global::intern("global_foo");
+        let bar = global::intern_static("global_bar");
+        assert_ne!(foo, bar);
+        assert_eq!(global::intern("global_foo"), foo);
+        assert_eq!(global::intern_static("global_bar"), bar);
+        assert_eq!(global::get("global_foo"), Some(foo));
+        assert_eq!(global::get("global_baz"), None);
+        assert_eq!(foo.as_str(), "global_foo");
+        assert_eq!(global::resolve(bar), Some("global_bar"));
+    }
+
+    #[test]
+    fn intern_macro_works() {
+        let symbols = (0..3)
+            .map(|_| intern!("global_macro"))
+            .collect::<Vec<GlobalSymbol>>();
+        assert!(symbols.iter().all(|&symbol| symbol == symbols[0]));
+        assert_eq!(symbols[0], global::intern("global_macro"));
+        assert_eq!(symbols[0].as_str(), "global_macro");
+    }
+
+    #[test]
+    fn fmt_works() {
+        let symbol = intern!("global_fmt");
+        assert_eq!(format!("{}", symbol), "global_fmt");
+        assert_eq!(format!("{:?}", symbol), "\"global_fmt\"");
+    }
+
+    #[test]
+    fn fmt_does_not_panic_for_uninterned_symbols() {
+        use string_interner::Symbol;
+        let index = u32::MAX as usize - 1;
+        let symbol = GlobalSymbol::try_from_usize(index).unwrap();
+        assert_eq!(global::resolve(symbol), None);
+        assert_eq!(format!("{}", symbol), index.to_string());
+        assert_eq!(format!("{:?}", symbol), format!("GlobalSymbol({})", index));
+    }
+
+    #[test]
+    fn intern_from_threads_works() {
+        let symbols = std::thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..100)
+                            .map(|i| global::intern(&format!("global_thread_{}", i)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        for (i, &symbol) in symbols[0].iter().enumerate() {
+            assert_eq!(symbol.as_str(), format!("global_thread_{}", i));
+            assert!(symbols.iter().all(|other| other[i] == symbol));
+        }
+    }
+}
+
+mod branded {
+    use string_interner::{
+        symbol::InlineSymbolU64,
+        StringInterner,
+    };
+
+    #[test]
+    fn branded_works() {
+        let mut interner = StringInterner::default();
+        let foo = interner.get_or_intern("foo");
+        let (interner, bar) = interner.branded(|mut interner| {
+            assert_eq!(interner.len(), 1);
+            let foo2 = interner.get_or_intern("foo");
+            let bar = interner.get_or_intern_static("bar");
+            assert_eq!(foo2.into_inner(), foo);
+            assert_eq!(interner.get("bar"), Some(bar));
+            assert_eq!(interner.get("baz"), None);
+            assert_eq!(interner.resolve(&foo2), "foo");
+            assert_eq!(interner.resolve(&bar), "bar");
+            (interner.into_inner(), bar.into_inner())
+        });
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.resolve(bar), Some("bar"));
+    }
+
+    #[test]
+    fn inline_symbols_work() {
+        let interner = StringInterner::<InlineSymbolU64>::new();
+        let (interner, long) = interner.branded(|mut interner| {
+            let x = interner.get_or_intern("x");
+            let long = interner.get_or_intern("identifier");
+            assert_eq!(interner.resolve(&x), "x");
+            assert_eq!(interner.resolve(&long), "identifier");
+            (interner.into_inner(), long.into_inner())
+        });
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.resolve(long), Some("identifier"));
+    }
+}
+
+mod frozen {
+    use std::{
+        sync::Arc,
+        thread,
+    };
+    use string_interner::{
+        FrozenInterner,
+        FrozenResolver,
+        StringInterner,
+    };
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn frozen_is_send_sync() {
+        assert_send_sync::<FrozenInterner>();
+        assert_send_sync::<FrozenResolver>();
+    }
+
+    #[test]
+    fn freeze_works() {
+        let mut interner = StringInterner::default();
+        let foo = interner.get_or_intern("foo");
+        let bar = interner.get_or_intern("bar");
+        let frozen = Arc::new(interner.freeze());
+        let handles = (0..4)
+            .map(|_| {
+                let frozen = Arc::clone(&frozen);
+                thread::spawn(move || {
+                    assert_eq!(frozen.len(), 2);
+                    assert_eq!(frozen.get("foo"), Some(foo));
+                    assert_eq!(frozen.get("baz"), None);
+                    assert_eq!(frozen.resolve(bar), Some("bar"));
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let frozen = Arc::try_unwrap(frozen).unwrap();
+        let strings = (&frozen).into_iter().collect::<Vec<_>>();
+        assert_eq!(strings, vec![(foo, "foo"), (bar, "bar")]);
+        let mut interner = frozen.unfreeze();
+        assert_eq!(interner.get_or_intern("foo"), foo);
+        let baz = interner.get_or_intern("baz");
+        assert_eq!(interner.resolve(baz), Some("baz"));
+    }
+
+    #[test]
+    fn into_resolver_works() {
+        let mut interner = StringInterner::default();
+        let foo = interner.get_or_intern("foo");
+        let resolver = interner.freeze().into_resolver();
+        assert_eq!(resolver.len(), 1);
+        assert!(!resolver.is_empty());
+        assert_eq!(resolver.resolve(foo), Some("foo"));
+        assert_eq!((&resolver).into_iter().count(), 1);
+    }
+}
+
+mod inline_symbol {
+    use super::*;
+    use string_interner::{
+        backend::Backend,
+        symbol::InlineSymbolU64,
+        Interner,
+        Resolver,
+        Snapshot,
+    };
+
+    type StringInterner<B> = Interner<str, InlineSymbolU64, B, DefaultHashBuilder>;
+
+    fn inline_strings_work<B>()
+    where
+        B: Backend<InlineSymbolU64> + Resolver<InlineSymbolU64>,
+    {
+        let mut interner = StringInterner::<B>::new();
+        let x = interner.get_or_intern("x");
+        let id = interner.get_or_intern("id");
+        let long = interner.get_or_intern("identifier");
+        assert_eq!(x.inline_str(), Some("x"));
+        assert_eq!(id.inline_str(), Some("id"));
+        assert_eq!(long.inline_str(), None);
+        // Only strings that are not encoded inline are stored.
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.get_or_intern(String::from("x")), x);
+        assert_eq!(interner.get_or_intern_static("identifier"), long);
+        assert_eq!(interner.get("id"), Some(id));
+        assert_eq!(interner.get("ab"), InlineSymbolU64::try_inline("ab"));
+        assert_eq!(interner.get("identifiers"), None);
+        assert_eq!(
+            interner.try_get_or_intern("1234567"),
+            Ok(InlineSymbolU64::try_inline("1234567").unwrap())
+        );
+        assert!(
+            matches!(interner.entry("y"), Entry::Occupied(symbol) if symbol.inline_str() == Some("y"))
+        );
+        assert!(!interner.get_or_intern_inserted("z").1);
+        assert_eq!(interner.resolve_ref(&x), Some("x"));
+        assert_eq!(interner.resolve_ref(&long), Some("identifier"));
+        assert_eq!(interner.resolve(x), None);
+        assert_eq!(interner.resolve(long), Some("identifier"));
+        let resolver: &dyn Resolver<InlineSymbolU64> = &interner;
+        assert_eq!(resolver.resolve_ref(&x), Some("x"));
+        let interner = interner.freeze();
+        assert_eq!(interner.resolve_ref(&id), Some("id"));
+        assert_eq!(Resolver::resolve_ref(&interner, &id), Some("id"));
+        let resolver = interner.into_resolver();
+        assert_eq!(resolver.resolve_ref(&id), Some("id"));
+        assert_eq!(resolver.resolve_ref(&long), Some("identifier"));
+    }
+
+    #[test]
+    fn snapshot_works() {
+        let mut interner =
+            StringInterner::<backend::StringBackend<InlineSymbolU64>>::new();
+        let x = interner.get_or_intern("x");
+        let long = interner.get_or_intern("identifier");
+        let bytes = interner.to_snapshot().unwrap();
+        let snapshot = Snapshot::<InlineSymbolU64>::from_bytes(&bytes).unwrap();
+        assert_eq!(snapshot.get("x"), Some(x));
+        assert_eq!(snapshot.get("identifier"), Some(long));
+        assert_eq!(snapshot.resolve_ref(&x), Some("x"));
+        assert_eq!(snapshot.resolve_ref(&long), Some("identifier"));
+    }
+
+    #[test]
+    fn bucket_backend_works() {
+        inline_strings_work::<backend::BucketBackend<InlineSymbolU64>>();
+    }
+
+    #[test]
+    fn buffer_backend_works() {
+        inline_strings_work::<backend::BufferBackend<InlineSymbolU64>>();
+    }
+
+    #[test]
+    fn string_backend_works() {
+        inline_strings_work::<backend::StringBackend<InlineSymbolU64>>();
+    }
+
+    #[test]
+    fn bytes_work() {
+        let mut interner = Interner::<
+            [u8],
+            InlineSymbolU64,
+            backend::StringBackend<InlineSymbolU64, [u8]>,
+        >::new();
+        let short = interner.get_or_intern(b"abc");
+        let invalid = interner.get_or_intern(b"\xFF");
+        assert_eq!(short.inline_str(), Some("abc"));
+        assert_eq!(invalid.inline_str(), None);
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.resolve_ref(&short), Some(&b"abc"[..]));
+        assert_eq!(interner.resolve_ref(&invalid), Some(&b"\xFF"[..]));
+    }
+
+    #[test]
+    fn merge_from_works() {
+        let mut source =
+            Interner::<str, DefaultSymbol, backend::StringBackend<DefaultSymbol>>::new();
+        let x = source.get_or_intern("x");
+        let long = source.get_or_intern("identifier");
+        let mut interner =
+            StringInterner::<backend::StringBackend<InlineSymbolU64>>::new();
+        let remap = interner.merge_from(&source);
+        assert_eq!(remap.get(x), InlineSymbolU64::try_inline("x"));
+        assert_eq!(interner.resolve(remap.apply(long)), Some("identifier"));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn remap_passes_inline_symbols_through() {
+        let mut source = StringInterner::<backend::StringBackend<InlineSymbolU64>>::new();
+        let x = source.get_or_intern("x");
+        let long = source.get_or_intern("identifier");
+        let mut interner =
+            StringInterner::<backend::BucketBackend<InlineSymbolU64>>::new();
+        interner.get_or_intern("other symbol");
+        let remap = interner.merge_from(&source);
+        assert_eq!(remap.apply(x), x);
+        let mut symbols = [x, long];
+        remap.apply_in_place(&mut symbols);
+        assert_eq!(interner.resolve_ref(&symbols[0]), Some("x"));
+        assert_eq!(interner.resolve_ref(&symbols[1]), Some("identifier"));
+        let mut target =
+            Interner::<str, DefaultSymbol, backend::StringBackend<DefaultSymbol>>::new();
+        let remap = target.merge_from(&source);
+        assert_eq!(remap.get(x), None);
+        let mapped = remap.apply_or_intern(&mut target, x);
+        assert_eq!(target.resolve(mapped), Some("x"));
+        assert_eq!(remap.apply_or_intern(&mut target, x), mapped);
+        let long = remap.apply_or_intern(&mut target, long);
+        assert_eq!(target.resolve(long), Some("identifier"));
+        assert_eq!(target.len(), 2);
+    }
+}
+
+mod value_backend {
+    use super::*;
+
+    gen_tests_for_backend!(backend::ValueBackend<DefaultSymbol, str>);
+
+    type ValueInterner<T> = string_interner::Interner<
+        T,
+        DefaultSymbol,
+        backend::ValueBackend<DefaultSymbol, T>,
+        DefaultHashBuilder,
+    >;
+
+    #[test]
+    fn tuples_work() {
+        let mut interner = ValueInterner::<(u32, &str)>::new();
+        let symbol_a = interner.get_or_intern_ref(&(1, "a"));
+        let symbol_b = interner.get_or_intern_ref(&(1, "b"));
+        assert_ne!(symbol_a, symbol_b);
+        assert_eq!(interner.get_or_intern_ref(&(1, "a")), symbol_a);
+        assert_eq!(interner.get_ref(&(1, "b")), Some(symbol_b));
+        assert_eq!(interner.get_ref(&(2, "a")), None);
+        assert_eq!(interner.resolve(symbol_a), Some(&(1, "a")));
+        assert_eq!(interner.resolve(symbol_b), Some(&(1, "b")));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn slices_work() {
+        let mut interner = ValueInterner::<[u32]>::new();
+        let symbol_a = interner.get_or_intern(vec![1, 2, 3]);
+        let symbol_b = interner.get_or_intern([4, 5]);
+        let symbol_c = interner.get_or_intern_static(&[]);
+        assert_eq!(interner.get_or_intern(&[1, 2, 3][..]), symbol_a);
+        assert_eq!(interner.get(vec![4, 5]), Some(symbol_b));
+        assert_eq!(interner.resolve(symbol_a), Some(&[1, 2, 3][..]));
+        assert_eq!(interner.resolve(symbol_c), Some(&[][..]));
+        let values = interner
+            .into_iter()
+            .map(|(symbol, value)| (symbol, value.to_vec()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                (symbol_a, vec![1, 2, 3]),
+                (symbol_b, vec![4, 5]),
+                (symbol_c, vec![])
+            ]
+        );
+    }
+}
+
+mod concurrent_interner {
+    use super::*;
+    use string_interner::ConcurrentStringInterner;
+
+    type Interner = ConcurrentStringInterner<DefaultSymbol, DefaultHashBuilder>;
+
+    #[test]
+    fn new_works() {
+        let interner = Interner::new();
+        assert_eq!(interner.len(), 0);
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    fn get_or_intern_works() {
+        let interner = Interner::new();
+        // Insert 3 unique strings:
+        let symbol_a = interner.get_or_intern("a");
+        let symbol_b = interner.get_or_intern("b");
+        let symbol_c = interner.get_or_intern_static("c");
+        assert_eq!(interner.len(), 3);
+        // Insert the same 3 unique strings, yield the same symbols:
+        assert_eq!(interner.get_or_intern("a"), symbol_a);
+        assert_eq!(interner.get_or_intern_static("b"), symbol_b);
+        assert_eq!(interner.get_or_intern("c"), symbol_c);
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn get_and_resolve_works() {
+        let interner = Interner::new();
+        let symbol_a = interner.get_or_intern("a");
+        let symbol_b = interner.get_or_intern("b");
+        assert_eq!(interner.get("a"), Some(symbol_a));
+        assert_eq!(interner.get("b"), Some(symbol_b));
+        assert_eq!(interner.get("c"), None);
+        assert_eq!(interner.resolve(symbol_a), Some("a"));
+        assert_eq!(interner.resolve(symbol_b), Some("b"));
+        assert_eq!(interner.resolve(expect_valid_symbol(2)), None);
+        assert_eq!(interner.resolve(expect_valid_symbol(1_000_000)), None);
+    }
+
+    #[test]
+    fn parallel_get_or_intern_works() {
+        let len_words = 10_000;
+        let words = (0..len_words).map(|i| i.to_string()).collect::<Vec<_>>();
+        let interner = Interner::new();
+        let symbols = std::thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        words
+                            .iter()
+                            .map(|word| {
+                                let symbol = interner.get_or_intern(word);
+                                assert_eq!(interner.resolve(symbol), Some(word.as_str()));
+                                symbol
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(interner.len(), len_words);
+        for thread_symbols in &symbols[1..] {
+            assert_eq!(thread_symbols, &symbols[0]);
+        }
+        for (word, &symbol) in words.iter().zip(&symbols[0]) {
+            assert_eq!(interner.get(word), Some(symbol));
+            assert_eq!(interner.resolve(symbol), Some(word.as_str()));
+        }
+    }
+}
+
+macro_rules! gen_bytes_tests_for_backend {
+    ( $backend:ty, $path_backend:ty ) => {
+        type BytesInterner =
+            string_interner::BytesInterner<DefaultSymbol, $backend, DefaultHashBuilder>;
+
+        #[test]
+        fn get_or_intern_works() {
+            let mut interner = BytesInterner::new();
+            // Insert 3 unique byte strings, one of them not valid UTF-8:
+            assert_eq!(interner.get_or_intern(b"a").to_usize(), 0);
+            assert_eq!(interner.get_or_intern(&[0xFF, 0xFE][..]).to_usize(), 1);
+            assert_eq!(interner.get_or_intern_static(b"").to_usize(), 2);
+            assert_eq!(interner.len(), 3);
+            // Insert the same 3 unique byte strings, yield the same symbols:
+            assert_eq!(interner.get_or_intern("a").to_usize(), 0);
+            assert_eq!(interner.get_or_intern(vec![0xFF, 0xFE]).to_usize(), 1);
+            assert_eq!(interner.get_or_intern(b"").to_usize(), 2);
+            assert_eq!(interner.len(), 3);
+        }
+
+        #[test]
+        fn get_and_resolve_works() {
+            let mut interner = BytesInterner::new();
+            let symbol_a = interner.get_or_intern(b"a");
+            let symbol_b = interner.get_or_intern(&[0xFF, 0xFE][..]);
+            assert_eq!(interner.get(b"a"), Some(symbol_a));
+            assert_eq!(interner.get(&[0xFF, 0xFE][..]), Some(symbol_b));
+            assert_eq!(interner.get(b"c"), None);
+            assert_eq!(interner.resolve(symbol_a), Some(&b"a"[..]));
+            assert_eq!(interner.resolve(symbol_b), Some(&[0xFF, 0xFE][..]));
+            assert_eq!(interner.resolve(expect_valid_symbol(2)), None);
+        }
+
+        #[test]
+        fn iter_works() {
+            let strings: [&[u8]; 3] = [b"a", &[0xFF], b"c"];
+            let interner = strings.iter().collect::<BytesInterner>();
+            let actual = interner
+                .into_iter()
+                .map(|(symbol, string)| (symbol.to_usize(), string))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                actual,
+                vec![(0, strings[0]), (1, strings[1]), (2, strings[2])]
+            );
+        }
+
+        #[test]
+        fn path_interner_works() {
+            use std::path::Path;
+            let mut interner = string_interner::Interner::<
+                Path,
+                DefaultSymbol,
+                $path_backend,
+                DefaultHashBuilder,
+            >::new();
+            let symbol_a = interner.get_or_intern("src/lib.rs");
+            let symbol_b = interner.get_or_intern(Path::new("src").join("interner.rs"));
+            assert_ne!(symbol_a, symbol_b);
+            assert_eq!(interner.get_or_intern(Path::new("src/lib.rs")), symbol_a);
+            assert_eq!(interner.resolve(symbol_a), Some(Path::new("src/lib.rs")));
+            assert_eq!(
+                interner.resolve(symbol_b),
+                Some(Path::new("src/interner.rs"))
+            );
+        }
+    };
+}
+
+mod bucket_backend_bytes {
+    use super::*;
+
+    gen_bytes_tests_for_backend!(
+        backend::BucketBackend<DefaultSymbol, [u8]>,
+        backend::BucketBackend<DefaultSymbol, std::path::Path>
+    );
+}
+
+mod simple_backend_bytes {
+    use super::*;
+
+    gen_bytes_tests_for_backend!(
+        backend::SimpleBackend<DefaultSymbol, [u8]>,
+        backend::SimpleBackend<DefaultSymbol, std::path::Path>
+    );
+}
+
+mod string_backend_bytes {
+    use super::*;
+
+    gen_bytes_tests_for_backend!(
+        backend::StringBackend<DefaultSymbol, [u8]>,
+        backend::StringBackend<DefaultSymbol, std::path::Path>
+    );
+}
+
+#[cfg(feature = "serde-1")]
+mod serde {
+    use string_interner::{
+        StringInterner,
+        Symbol,
+    };
+
+    #[test]
+    fn deserialize_interns_in_order() {
+        let strings = (0..100).map(|i| (i % 70).to_string()).collect::<Vec<_>>();
+        let json = serde_json::to_string(&strings).unwrap();
+        let interner: StringInterner = serde_json::from_str(&json).unwrap();
+        assert_eq!(interner.len(), 70);
+        for (i, string) in strings.iter().take(70).enumerate() {
+            assert_eq!(
+                interner.get(string).map(|symbol| symbol.to_usize()),
+                Some(i)
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_reports_invalid_elements() {
+        assert!(serde_json::from_str::<StringInterner>(r#"["a", 1, "b"]"#).is_err());
+    }
+}
+
+#[cfg(feature = "serde-1")]
+mod serde_with_symbols {
+    use super::*;
+    use string_interner::{
+        with_symbols,
+        StringInterner,
+    };
+
+    fn from_json<I>(json: &str) -> Result<I, serde_json::Error>
+    where
+        I: for<'de> with_symbols::FromSymbolPairs<'de>,
+    {
+        with_symbols::deserialize(&mut serde_json::Deserializer::from_str(json))
+    }
+
+    fn to_json(interner: &StringInterner) -> String {
+        let mut buffer = Vec::new();
+        with_symbols::serialize(interner, &mut serde_json::Serializer::new(&mut buffer))
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn round_trip_preserves_symbols() {
+        let mut interner = StringInterner::new();
+        let symbols = ["a", "b", "c", "a"]
+            .iter()
+            .map(|s| interner.get_or_intern(s))
+            .collect::<Vec<_>>();
+        let json = to_json(&interner);
+        assert_eq!(json, r#"[[0,"a"],[1,"b"],[2,"c"]]"#);
+        let restored = from_json::<StringInterner>(&json).unwrap();
+        assert_eq!(restored, interner);
+        for (symbol, string) in symbols.into_iter().zip(["a", "b", "c", "a"]) {
+            assert_eq!(restored.resolve(symbol), Some(string));
+        }
+    }
+
+    #[test]
+    fn rejects_duplicate_symbols() {
+        let error = from_json::<StringInterner>(r#"[[0,"a"],[0,"b"]]"#).unwrap_err();
+        assert!(error.to_string().contains("duplicate symbol 0"));
+    }
+
+    #[test]
+    fn rejects_duplicate_strings() {
+        let error = from_json::<StringInterner>(r#"[[0,"a"],[1,"a"]]"#).unwrap_err();
+        assert!(error.to_string().contains("duplicate string for symbol 1"));
+    }
+
+    #[test]
+    fn rejects_gaps() {
+        let error = from_json::<StringInterner>(r#"[[0,"a"],[2,"b"]]"#).unwrap_err();
+        assert!(error.to_string().contains("gap in symbols"));
+    }
+
+    #[test]
+    fn rejects_out_of_range_symbols() {
+        type Interner16 = string_interner::Interner<
+            str,
+            SymbolU16,
+            backend::StringBackend<SymbolU16>,
+            DefaultHashBuilder,
+        >;
+        let error = from_json::<Interner16>(r#"[[70000,"a"]]"#).unwrap_err();
+        assert!(error.to_string().contains("out of range"));
+    }
+}
+
+mod snapshot {
+    use super::*;
+    use string_interner::{
+        Snapshot,
+        SnapshotError,
+        StringInterner,
+    };
+
+    fn sample() -> (StringInterner, Vec<u8>) {
+        let interner = ["foo", "bar", "", "baz", "ünïcödé", "foo"]
+            .iter()
+            .collect::<StringInterner>();
+        let bytes = interner.to_snapshot().unwrap();
+        (interner, bytes)
+    }
+
+    #[test]
+    fn round_trip_works() {
+        let (interner, bytes) = sample();
+        let snapshot = Snapshot::<DefaultSymbol>::from_bytes(&bytes).unwrap();
+        assert_eq!(snapshot.len(), interner.len());
+        for (symbol, string) in &interner {
+            assert_eq!(snapshot.get(string), Some(symbol));
+            assert_eq!(snapshot.resolve(symbol), Some(string));
+        }
+        assert_eq!(snapshot.get("qux"), None);
+        assert_eq!(
+            snapshot.iter().collect::<Vec<_>>(),
+            interner.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn snapshot_resolver_works() {
+        use string_interner::Resolver;
+
+        let (interner, bytes) = sample();
+        let snapshot = Snapshot::<DefaultSymbol>::from_bytes(&bytes).unwrap();
+        let resolver: &dyn Resolver<DefaultSymbol> = &snapshot;
+        assert_eq!(resolver.len(), interner.len());
+        for (symbol, string) in &interner {
+            assert_eq!(resolver.resolve(symbol), Some(string));
+        }
+        assert_eq!(
+            resolver.iter().collect::<Vec<_>>(),
+            interner.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn empty_works() {
+        let bytes = StringInterner::default().to_snapshot().unwrap();
+        let snapshot = Snapshot::<DefaultSymbol>::from_bytes(&bytes).unwrap();
+        assert!(snapshot.is_empty());
+        assert_eq!(snapshot.get(""), None);
+        assert_eq!(snapshot.iter().next(), None);
+    }
+
+    #[test]
+    fn write_snapshot_works() {
+        let (interner, bytes) = sample();
+        let mut written = Vec::new();
+        interner.write_snapshot(&mut written).unwrap();
+        assert_eq!(written, bytes);
+    }
+
+    #[test]
+    fn rejects_invalid_data() {
+        let (_, bytes) = sample();
+        let load =
+            |bytes: &[u8]| Snapshot::<DefaultSymbol>::from_bytes(bytes).map(|_| ());
+        let mut invalid = bytes.clone();
+        invalid[0] = b'X';
+        assert_eq!(load(&invalid), Err(SnapshotError::InvalidMagic));
+        let mut invalid = bytes.clone();
+        invalid[8] = 2;
+        assert_eq!(load(&invalid), Err(SnapshotError::UnsupportedVersion(2)));
+        assert_eq!(
+            load(&bytes[..bytes.len() - 1]),
+            Err(SnapshotError::Truncated)
+        );
+        let mut invalid = bytes.clone();
+        *invalid.last_mut().unwrap() = 0xFF;
+        assert_eq!(load(&invalid), Err(SnapshotError::InvalidUtf8));
+        let mut invalid = bytes.clone();
+        // Points the end of the first string past the end of the buffer.
+        invalid[24] = 0xFF;
+        assert_eq!(load(&invalid), Err(SnapshotError::InvalidLayout));
+    }
+
+    #[test]
+    fn rejects_too_small_symbols() {
+        let interner = (0..=u16::MAX as u32 + 1)
+            .map(|i| i.to_string())
+            .collect::<StringInterner>();
+        let bytes = interner.to_snapshot().unwrap();
+        assert_eq!(
+            Snapshot::<SymbolU16>::from_bytes(&bytes).map(|_| ()),
+            Err(SnapshotError::SymbolsExhausted)
+        );
+        assert!(Snapshot::<DefaultSymbol>::from_bytes(&bytes).is_ok());
+    }
 }